@@ -1 +1,77 @@
-fn main() {}
+use std::{path::Path, process};
+
+use advent_of_code::template::{
+    commands::new_year,
+    years::{discover, run_all, Year},
+};
+
+enum AppArguments {
+    /// Create a new year crate from `template/`.
+    NewYear { year: u16 },
+    /// Run a year binary command for one or more years, e.g.
+    /// `cargo years --year 2024 time --all` or `cargo years --all-years verify`.
+    Run { years: Vec<Year>, args: Vec<String> },
+}
+
+fn main() {
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("advent_of_code crate should be in a workspace");
+
+    match parse(workspace_root) {
+        Ok(AppArguments::NewYear { year }) => new_year::handle(workspace_root, year),
+        Ok(AppArguments::Run { years, args }) => run_all(&years, &args),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn parse(workspace_root: &Path) -> Result<AppArguments, Box<dyn std::error::Error>> {
+    let mut args = pico_args::Arguments::from_env();
+
+    let command = args.subcommand()?;
+
+    if command.as_deref() == Some("new-year") {
+        return Ok(AppArguments::NewYear {
+            year: args.free_from_str()?,
+        });
+    }
+
+    let selected: Vec<u16> = args.values_from_str("--year")?;
+    let all_years = args.contains("--all-years");
+
+    let args = command
+        .into_iter()
+        .map(Ok)
+        .chain(args.finish().into_iter().map(|arg| {
+            arg.into_string()
+                .map_err(|_| "arguments must be valid UTF-8.")
+        }))
+        .collect::<Result<Vec<String>, _>>()?;
+
+    if args.is_empty() {
+        return Err("missing command to run for each year.".into());
+    }
+
+    let available = discover(workspace_root)?;
+
+    let years = match (all_years, selected.is_empty()) {
+        (true, true) => available,
+        (false, false) => selected
+            .iter()
+            .map(|year| {
+                available
+                    .iter()
+                    .find(|y| y.year == *year)
+                    .cloned()
+                    .ok_or(format!("no crate found for year {year}."))
+            })
+            .collect::<Result<_, _>>()?,
+        (true, false) => return Err("`--year` and `--all-years` are mutually exclusive.".into()),
+        (false, true) => return Err("either `--year <year>` or `--all-years` is required.".into()),
+    };
+
+    Ok(AppArguments::Run { years, args })
+}
@@ -0,0 +1,576 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the accepted answers for a single day, along with rejected guesses.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub wrong: Vec<WrongAnswer>,
+}
+
+/// A submission that was rejected by the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrongAnswer {
+    pub part: u8,
+    pub value: String,
+    pub hint: Option<Hint>,
+}
+
+/// The too high / too low hint that accompanies some wrong answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Hint {
+    fn as_str(self) -> &'static str {
+        match self {
+            Hint::TooHigh => "too_high",
+            Hint::TooLow => "too_low",
+        }
+    }
+}
+
+impl FromStr for Hint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too_high" => Ok(Hint::TooHigh),
+            "too_low" => Ok(Hint::TooLow),
+            s => Err(format!("unknown hint: {s}")),
+        }
+    }
+}
+
+impl Answer {
+    fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Outcome of comparing a solution result against the stored answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected } => write!(f, "fail (expected {expected})"),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Reason for refusing to submit a result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadyAccepted { answer: String },
+    KnownWrong,
+    NotBelow { bound: String },
+    NotAbove { bound: String },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyAccepted { answer } => {
+                write!(f, "this part was already solved with {answer}.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer was already rejected."),
+            Rejection::NotBelow { bound } => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Rejection::NotAbove { bound } => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "pass" => Ok(Verdict::Pass),
+            "missing" => Ok(Verdict::Missing),
+            s => s
+                .strip_prefix("fail (expected ")
+                .and_then(|s| s.strip_suffix(')'))
+                .map(|expected| Verdict::Fail {
+                    expected: expected.to_string(),
+                })
+                .ok_or_else(|| format!("could not parse verdict from: {s}")),
+        }
+    }
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Get the accepted answer for one part of a day, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day)
+            .and_then(|a| a.part(part))
+            .map(String::as_str)
+    }
+
+    /// Record the accepted answer for one part of a day, replacing any previous value.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let entry = self.entry(day);
+        match part {
+            1 => entry.part_1 = Some(answer.to_string()),
+            2 => entry.part_2 = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+
+    /// Record a rejected submission for one part of a day.
+    pub fn add_wrong(&mut self, day: Day, part: u8, value: &str, hint: Option<Hint>) {
+        let wrong = WrongAnswer {
+            part,
+            value: value.to_string(),
+            hint,
+        };
+
+        let entry = self.entry(day);
+        if !entry.wrong.contains(&wrong) {
+            entry.wrong.push(wrong);
+        }
+    }
+
+    /// Check a result against what is known about one part of a day before submitting it.
+    pub fn check_submission(&self, day: Day, part: u8, value: &str) -> Result<(), Rejection> {
+        let Some(entry) = self.data.iter().find(|a| a.day == day) else {
+            return Ok(());
+        };
+
+        if let Some(answer) = entry.part(part) {
+            return Err(Rejection::AlreadyAccepted {
+                answer: answer.clone(),
+            });
+        }
+
+        let number = value.parse::<i128>().ok();
+
+        for wrong in entry.wrong.iter().filter(|w| w.part == part) {
+            if wrong.value == value {
+                return Err(Rejection::KnownWrong);
+            }
+
+            let (Some(number), Ok(bound)) = (number, wrong.value.parse::<i128>()) else {
+                continue;
+            };
+
+            match wrong.hint {
+                Some(Hint::TooHigh) if number >= bound => {
+                    return Err(Rejection::NotBelow {
+                        bound: wrong.value.clone(),
+                    });
+                }
+                Some(Hint::TooLow) if number <= bound => {
+                    return Err(Rejection::NotAbove {
+                        bound: wrong.value.clone(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn entry(&mut self, day: Day) -> &mut Answer {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                    wrong: vec![],
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        &mut self.data[index]
+    }
+
+    /// Compare a solution result with the accepted answer for one part of a day.
+    pub fn verify(&self, day: Day, part: u8, result: Option<&str>) -> Verdict {
+        match (self.get(day, part), result) {
+            (Some(expected), Some(result)) if expected == result => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            (None, _) => Verdict::Missing,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "wrong".into(),
+            JsonValue::Array(value.wrong.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        // NOTE: `wrong` is optional so that hand-written answer files stay valid.
+        let wrong = match json.get("wrong") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected answer.wrong to be an array.")?
+                .iter()
+                .map(WrongAnswer::try_from)
+                .collect::<Result<_, _>>()?,
+            None => vec![],
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+            wrong,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&WrongAnswer> for JsonValue {
+    fn from(value: &WrongAnswer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("value".into(), JsonValue::String(value.value.clone()));
+        map.insert(
+            "hint".into(),
+            value.hint.map_or(JsonValue::Null, |hint| {
+                JsonValue::String(hint.as_str().into())
+            }),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for WrongAnswer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected wrong answer to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| *part == 1 || *part == 2)
+            .ok_or("Expected wrong_answer.part to be 1 or 2.")?;
+
+        let value = json
+            .get("value")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected wrong_answer.value to be a string.")?;
+
+        let hint = match json.get("hint") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected wrong_answer.hint to be null or string.")?
+                    .parse()?,
+            ),
+            _ => None,
+        };
+
+        Ok(WrongAnswer {
+            part,
+            value: value.clone(),
+            hint,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answer, Answers, Hint, Rejection, Verdict};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("142".into()),
+                    part_2: Some("281".into()),
+                    wrong: vec![],
+                },
+                Answer {
+                    day: day!(3),
+                    part_1: Some("4361".into()),
+                    part_2: None,
+                    wrong: vec![],
+                },
+            ],
+        }
+    }
+
+    mod deserialization {
+        use crate::{
+            day,
+            template::answers::{Answers, Hint},
+        };
+
+        #[test]
+        fn handles_json_answers() {
+            let json =
+                r#"{ "data": [{ "day": "01", "part_1": "142", "part_2": null }] }"#.to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            assert_eq!(answers.get(day!(1), 1), Some("142"));
+            assert_eq!(answers.get(day!(1), 2), None);
+            assert!(answers.data[0].wrong.is_empty());
+        }
+
+        #[test]
+        fn handles_wrong_answers() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "wrong": [{ "part": 1, "value": "10", "hint": "too_low" }, { "part": 1, "value": "abc", "hint": null }] }] }"#
+                .to_string();
+            let answers = Answers::try_from(json).unwrap();
+            let wrong = &answers.data[0].wrong;
+            assert_eq!(wrong.len(), 2);
+            assert_eq!(wrong[0].hint, Some(Hint::TooLow));
+            assert_eq!(wrong[1].value, "abc");
+            assert_eq!(wrong[1].hint, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_answers() {
+            let json = r#"{ "data": [{ "day": "01" }] }"#.to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verify(day!(1), 1, Some("142")), Verdict::Pass);
+        assert_eq!(
+            answers.verify(day!(1), 2, Some("280")),
+            Verdict::Fail {
+                expected: "281".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(1), 2, None),
+            Verdict::Fail {
+                expected: "281".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(3), 2, Some("1")), Verdict::Missing);
+        assert_eq!(answers.verify(day!(2), 1, Some("1")), Verdict::Missing);
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = get_mock_answers();
+        answers.set(day!(2), 2, "10");
+        answers.set(day!(3), 2, "467835");
+        assert_eq!(answers.data.len(), 3);
+        assert_eq!(answers.data[1].day, day!(2));
+        assert_eq!(answers.get(day!(2), 2), Some("10"));
+        assert_eq!(answers.get(day!(3), 2), Some("467835"));
+    }
+
+    #[test]
+    fn refuses_known_submissions() {
+        let mut answers = get_mock_answers();
+        answers.add_wrong(day!(3), 2, "100", Some(Hint::TooLow));
+        answers.add_wrong(day!(3), 2, "500", Some(Hint::TooHigh));
+        answers.add_wrong(day!(3), 2, "250", None);
+
+        assert_eq!(
+            answers.check_submission(day!(3), 1, "1"),
+            Err(Rejection::AlreadyAccepted {
+                answer: "4361".into()
+            })
+        );
+        assert_eq!(
+            answers.check_submission(day!(3), 2, "250"),
+            Err(Rejection::KnownWrong)
+        );
+        assert_eq!(
+            answers.check_submission(day!(3), 2, "100"),
+            Err(Rejection::KnownWrong)
+        );
+        assert_eq!(
+            answers.check_submission(day!(3), 2, "99"),
+            Err(Rejection::NotAbove {
+                bound: "100".into()
+            })
+        );
+        assert_eq!(
+            answers.check_submission(day!(3), 2, "501"),
+            Err(Rejection::NotBelow {
+                bound: "500".into()
+            })
+        );
+        assert_eq!(answers.check_submission(day!(3), 2, "300"), Ok(()));
+        assert_eq!(answers.check_submission(day!(2), 1, "1"), Ok(()));
+    }
+}
@@ -0,0 +1,374 @@
+//! Built-in HTTP client for adventofcode.com.
+//! Reads the session cookie from `ADVENT_OF_CODE_SESSION`, or from `~/.adventofcode.session` like aoc-cli does.
+//! Requests are spaced out by at least [`MIN_REQUEST_INTERVAL`], also across invocations.
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{markdown, AocClient, AocClientError, Puzzle, SubmitVerdict};
+use crate::template::{active_year, Day};
+
+/// Environment variable that holds the session cookie, same as for aoc-cli.
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+/// If set, requests go to this URL instead of `https://adventofcode.com`, e.g. a local mock server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// If set, replaces the default User-Agent. Advent of Code asks for contact details in it, e.g. an email address.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+/// Minimum time between two requests to adventofcode.com.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const DEFAULT_USER_AGENT: &str = concat!("github.com/BakerNet/aoc v", env!("CARGO_PKG_VERSION"));
+
+/// Read the session cookie from `ADVENT_OF_CODE_SESSION`, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+/// Empty values are skipped, so that the next source is tried.
+#[must_use]
+pub fn read_session() -> Option<String> {
+    let non_empty = |session: String| Some(session.trim().to_string()).filter(|s| !s.is_empty());
+
+    if let Some(session) = env::var(SESSION_ENV).ok().and_then(non_empty) {
+        return Some(session);
+    }
+
+    let home = PathBuf::from(env::var_os("HOME")?);
+    [
+        home.join(".adventofcode.session"),
+        home.join(".config").join("adventofcode.session"),
+    ]
+    .into_iter()
+    .find_map(|path| fs::read_to_string(path).ok().and_then(non_empty))
+}
+
+/// Spaces out requests by storing the time of the last request in a file.
+pub struct Throttle {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    #[must_use]
+    pub fn new(path: PathBuf, interval: Duration) -> Self {
+        Self { path, interval }
+    }
+
+    /// Wait until the interval since the last request has passed, then record a new request.
+    fn wait(&self) {
+        let now = SystemTime::now();
+
+        let last_request = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|millis| millis.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        if let Some(elapsed) = last_request.and_then(|last| now.duration_since(last).ok()) {
+            if let Some(remaining) = self.interval.checked_sub(elapsed) {
+                println!(
+                    "Waiting {}ms before the next request...",
+                    remaining.as_millis()
+                );
+                thread::sleep(remaining);
+            }
+        }
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        // throttling is best-effort, failing to record a request should not fail it.
+        let _ = fs::write(&self.path, millis.to_string());
+    }
+}
+
+pub struct HttpClient {
+    agent: ureq::Agent,
+    base_url: String,
+    year: Option<u16>,
+    session: Option<String>,
+    throttle: Throttle,
+}
+
+impl HttpClient {
+    #[must_use]
+    pub fn new(
+        base_url: &str,
+        year: Option<u16>,
+        session: Option<String>,
+        user_agent: &str,
+        throttle: Throttle,
+    ) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(user_agent)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            year,
+            session,
+            throttle,
+        }
+    }
+
+    /// The client for the active year, configured through the environment.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = env::var(USER_AGENT_ENV).unwrap_or_else(|_| {
+            eprintln!(
+                "Warning: `{USER_AGENT_ENV}` is not set. Please set it to a User-Agent with your contact details, \
+                e.g. \"{DEFAULT_USER_AGENT} by you@example.com\"."
+            );
+            DEFAULT_USER_AGENT.into()
+        });
+        let throttle = Throttle::new(
+            env::temp_dir().join("aoc-last-request"),
+            MIN_REQUEST_INTERVAL,
+        );
+
+        Self::new(
+            &base_url,
+            active_year(),
+            read_session(),
+            &user_agent,
+            throttle,
+        )
+    }
+
+    fn day_url(&self, day: Day) -> Result<String, AocClientError> {
+        let year = self.year.ok_or(AocClientError::MissingYear)?;
+        Ok(format!("{}/{year}/day/{}", self.base_url, day.into_inner()))
+    }
+
+    fn cookie(&self) -> Result<String, AocClientError> {
+        let session = self
+            .session
+            .as_ref()
+            .ok_or(AocClientError::MissingSession)?;
+        Ok(format!("session={session}"))
+    }
+
+    fn send(
+        &self,
+        day: Day,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, AocClientError> {
+        let request = request.set("Cookie", &self.cookie()?);
+        self.throttle.wait();
+
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| AocClientError::Http(format!("could not read response: {e}"))),
+            Err(ureq::Error::Status(404, _)) => Err(AocClientError::Http(format!(
+                "day {day} is not available (yet)."
+            ))),
+            Err(ureq::Error::Status(code @ (400 | 500), _)) => Err(AocClientError::Http(format!(
+                "request failed with status {code}, is the session cookie valid?"
+            ))),
+            Err(ureq::Error::Status(code, _)) => Err(AocClientError::Http(format!(
+                "request failed with status {code}."
+            ))),
+            Err(ureq::Error::Transport(e)) => Err(AocClientError::Http(e.to_string())),
+        }
+    }
+}
+
+impl AocClient for HttpClient {
+    fn fetch(&self, day: Day) -> Result<Puzzle, AocClientError> {
+        let url = self.day_url(day)?;
+
+        let html = self.send(day, self.agent.get(&url), None)?;
+        let input = self.send(day, self.agent.get(&format!("{url}/input")), None)?;
+
+        Ok(Puzzle {
+            input,
+            description: markdown::from_html(&html),
+        })
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day)?);
+        let level = part.to_string();
+
+        let html = self.send(
+            day,
+            self.agent.post(&url),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+
+        let message = markdown::from_html(&html);
+        println!("{message}");
+        Ok(SubmitVerdict::from_output(&message))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        process,
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use super::{HttpClient, Throttle};
+    use crate::{
+        day,
+        template::answers::Hint,
+        template::aoc_client::{AocClient, AocClientError, SubmitVerdict},
+    };
+
+    /// Serves one canned response per request, in order, and returns the requests it received.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut request = String::new();
+                    let mut content_length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                            content_length = length.trim().parse().unwrap();
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        request.push_str(&line);
+                    }
+
+                    let mut body_in = vec![0; content_length];
+                    reader.read_exact(&mut body_in).unwrap();
+                    request.push_str(&String::from_utf8(body_in).unwrap());
+
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    request
+                })
+                .collect()
+        });
+
+        (url, handle)
+    }
+
+    fn client(url: &str, name: &str) -> HttpClient {
+        let throttle_path = env::temp_dir().join(format!("aoc-http-{name}-{}", process::id()));
+        let _ = fs::remove_file(&throttle_path);
+
+        HttpClient::new(
+            url,
+            Some(2024),
+            Some("abc".into()),
+            "test-agent",
+            Throttle::new(throttle_path, Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn fetches_puzzles() {
+        let (url, server) = mock_server(vec![
+            (
+                200,
+                "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2><p>Hi <em>there</em></p></article></main>",
+            ),
+            (200, "1\n2\n"),
+        ]);
+
+        let puzzle = client(&url, "fetch").fetch(day!(1)).unwrap();
+        assert_eq!(puzzle.input, "1\n2\n");
+        assert_eq!(puzzle.description, "## --- Day 1 ---\n\nHi *there*\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1 HTTP/1.1"));
+        assert!(requests[1].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        for request in requests {
+            assert!(request.contains("Cookie: session=abc"));
+            assert!(request.to_lowercase().contains("user-agent: test-agent"));
+        }
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        )]);
+
+        let verdict = client(&url, "submit").submit(day!(5), 2, "42").unwrap();
+        assert_eq!(verdict, SubmitVerdict::Incorrect(Some(Hint::TooLow)));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/5/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_http_errors() {
+        let (url, server) = mock_server(vec![(404, ""), (400, "")]);
+        let client = client(&url, "errors");
+
+        let error = client.fetch(day!(25)).unwrap_err();
+        assert_eq!(error.to_string(), "day 25 is not available (yet).");
+
+        let error = client.submit(day!(1), 1, "1").unwrap_err();
+        assert!(error.to_string().contains("session cookie"));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn requires_session() {
+        let client = HttpClient::new(
+            "http://127.0.0.1:1",
+            Some(2024),
+            None,
+            "test-agent",
+            Throttle::new(env::temp_dir().join("aoc-http-unused"), Duration::ZERO),
+        );
+        assert!(matches!(
+            client.fetch(day!(1)),
+            Err(AocClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let path = env::temp_dir().join(format!("aoc-http-throttle-{}", process::id()));
+        let _ = fs::remove_file(&path);
+        let throttle = Throttle::new(path.clone(), Duration::from_millis(200));
+
+        let start = Instant::now();
+        throttle.wait();
+        assert!(start.elapsed() < Duration::from_millis(200));
+        throttle.wait();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
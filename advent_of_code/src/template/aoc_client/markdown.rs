@@ -0,0 +1,214 @@
+//! Converts the HTML of puzzle pages and submission responses to markdown.
+//! Only the `<article>` elements are kept, and only the handful of tags that Advent of Code uses in them is handled.
+
+/// The markdown of all `<article>` elements in a page, or of the whole page if it has none.
+pub fn from_html(html: &str) -> String {
+    let articles = articles(html);
+
+    let markdown = if articles.is_empty() {
+        to_markdown(html)
+    } else {
+        articles
+            .iter()
+            .map(|article| to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!("{}\n", markdown.trim_end())
+}
+
+/// The inner HTML of each `<article>` element.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(close) = rest[open_end..].find("</article>").map(|i| open_end + i) else {
+            break;
+        };
+
+        articles.push(&rest[open_end..close]);
+        rest = &rest[close + "</article>".len()..];
+    }
+
+    articles
+}
+
+enum Token<'a> {
+    Open(&'a str, &'a str),
+    Close(&'a str),
+    Text(&'a str),
+}
+
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+
+        let tag = rest[start + 1..end].trim_end_matches('/').trim();
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim()));
+        } else {
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Open(name, attributes));
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    tokens
+}
+
+fn to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+
+    for token in tokens(html) {
+        match token {
+            Token::Text(text) => markdown.push_str(&decode_entities(text)),
+            // markup inside of code blocks is dropped, e.g. `<em>` highlighting.
+            Token::Open(_, _) | Token::Close(_) if in_pre => {
+                if matches!(token, Token::Close("pre")) {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+            }
+            Token::Open(name, attributes) => match name {
+                "h2" => markdown.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    markdown.push_str("```\n");
+                }
+                "code" => markdown.push('`'),
+                "em" => markdown.push('*'),
+                "li" => markdown.push_str("- "),
+                "br" => markdown.push('\n'),
+                "a" => {
+                    links.push(attribute(attributes, "href").unwrap_or_default());
+                    markdown.push('[');
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name {
+                "h2" | "p" => markdown.push_str("\n\n"),
+                "code" => markdown.push('`'),
+                "em" => markdown.push('*'),
+                "li" => markdown.push('\n'),
+                "ul" => markdown.push('\n'),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    markdown.push_str(&format!("]({href})"));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    markdown
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attributes[start..].find('"')? + start;
+    Some(decode_entities(&attributes[start..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let replacement = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => name
+                    .strip_prefix('#')
+                    .and_then(|n| n.parse::<u32>().ok())
+                    .and_then(char::from_u32)?,
+            };
+            Some((c, end))
+        });
+
+        match replacement {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::from_html;
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = concat!(
+            "<html><body><main>",
+            "<article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2>",
+            "<p>The <em>Chief Historian</em> is <a href=\"https://example.com?a=1&amp;b=2\">missing</a>.</p>",
+            "<pre><code>3   4\n<em>4</em>   3\n</code></pre>",
+            "<ul><li>Pair up <code>1 &lt; 2</code>.</li><li>Sum it.</li></ul>",
+            "</article>",
+            "<p>Your puzzle answer was <code>11</code>.</p>",
+            "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Again.</p></article>",
+            "</main></body></html>",
+        );
+
+        assert_eq!(
+            from_html(html),
+            concat!(
+                "## --- Day 1: Historian Hysteria ---\n\n",
+                "The *Chief Historian* is [missing](https://example.com?a=1&b=2).\n\n",
+                "```\n3   4\n4   3\n```\n\n",
+                "- Pair up `1 < 2`.\n- Sum it.\n\n",
+                "\n",
+                "## --- Part Two ---\n\n",
+                "Again.\n",
+            )
+        );
+    }
+
+    #[test]
+    fn converts_pages_without_articles() {
+        assert_eq!(from_html("<p>That&#39;s it.</p>"), "That's it.\n");
+    }
+}
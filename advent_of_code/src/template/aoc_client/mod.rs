@@ -0,0 +1,454 @@
+/// Access to adventofcode.com, and offline stand-ins for it.
+///
+/// Commands talk to adventofcode.com through an [`AocClient`]:
+/// - [`HttpClient`] is the built-in HTTP client (see [`http`]).
+/// - [`StubServer`] serves puzzles and checks answers from a local directory, it is used if `AOC_STUB_SERVER` is set.
+/// - [`Cache`] wraps either of them, so that a puzzle is only fetched once.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{answers::Hint, Day};
+
+pub use http::HttpClient;
+
+pub mod http;
+mod markdown;
+
+/// If set, puzzles are served and answers are checked from this directory instead of adventofcode.com.
+pub const STUB_SERVER_ENV: &str = "AOC_STUB_SERVER";
+
+/// If set, fetched puzzles are cached in this directory instead of `data/cache`.
+pub const CACHE_DIR_ENV: &str = "AOC_CACHE_DIR";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found, see [`http::read_session`].
+    MissingSession,
+    /// No year is configured, i.e. `AOC_YEAR` is not set.
+    MissingYear,
+    Http(String),
+    Io(PathBuf, io::Error),
+    Stub(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `{}` or write it to \"~/.adventofcode.session\".",
+                http::SESSION_ENV
+            ),
+            AocClientError::MissingYear => write!(f, "no year configured, `AOC_YEAR` is not set."),
+            AocClientError::Http(e) => write!(f, "{e}"),
+            AocClientError::Io(path, e) => {
+                write!(f, "could not access \"{}\": {e}", path.display())
+            }
+            AocClientError::Stub(e) => write!(f, "stub server: {e}"),
+        }
+    }
+}
+
+/// The server's response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmitVerdict {
+    Correct,
+    Incorrect(Option<Hint>),
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+impl SubmitVerdict {
+    /// Classify the message the server responds with after submitting an answer.
+    pub fn from_output(output: &str) -> Self {
+        let output = output.to_lowercase();
+
+        if output.contains("that's the right answer") {
+            SubmitVerdict::Correct
+        } else if output.contains("that's not the right answer") {
+            let hint = if output.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if output.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            SubmitVerdict::Incorrect(hint)
+        } else if output.contains("gave an answer too recently") {
+            SubmitVerdict::TooRecent
+        } else if output.contains("solving the right level") {
+            SubmitVerdict::WrongLevel
+        } else {
+            SubmitVerdict::Unknown
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The input and the description of a puzzle, as markdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub input: String,
+    pub description: String,
+}
+
+/// The network interaction with adventofcode.com.
+pub trait AocClient {
+    /// Fetch the input and description of a day.
+    fn fetch(&self, day: Day) -> Result<Puzzle, AocClientError>;
+
+    /// Submit an answer for a part of a day.
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, AocClientError>;
+}
+
+impl<C: AocClient + ?Sized> AocClient for Box<C> {
+    fn fetch(&self, day: Day) -> Result<Puzzle, AocClientError> {
+        (**self).fetch(day)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, AocClientError> {
+        (**self).submit(day, part, answer)
+    }
+}
+
+/// The client used by commands: the stub server if `AOC_STUB_SERVER` is set, the HTTP client otherwise, behind the puzzle cache.
+#[must_use]
+pub fn client() -> Cache<Box<dyn AocClient>> {
+    let inner: Box<dyn AocClient> = match env::var_os(STUB_SERVER_ENV) {
+        Some(dir) => Box::new(StubServer::new(dir)),
+        None => Box::new(HttpClient::from_env()),
+    };
+
+    let cache_dir =
+        env::var_os(CACHE_DIR_ENV).map_or_else(|| PathBuf::from("data/cache"), PathBuf::from);
+    Cache::new(cache_dir, inner)
+}
+
+/// Fetch a day and write its input and description to `data/inputs/NN.txt` and `data/puzzles/NN.md`.
+pub fn download(client: &impl AocClient, day: Day, data_dir: &Path) -> Result<(), AocClientError> {
+    let puzzle = client.fetch(day)?;
+
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let puzzle_path = data_dir.join("puzzles").join(format!("{day}.md"));
+    write_file(&input_path, &puzzle.input)?;
+    write_file(&puzzle_path, &puzzle.description)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Fetch the description of a day.
+pub fn read(client: &impl AocClient, day: Day) -> Result<String, AocClientError> {
+    Ok(client.fetch(day)?.description)
+}
+
+fn read_file(path: &Path) -> Result<String, AocClientError> {
+    fs::read_to_string(path).map_err(|e| AocClientError::Io(path.to_path_buf(), e))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AocClientError::Io(parent.to_path_buf(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| AocClientError::Io(path.to_path_buf(), e))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Keeps fetched puzzles in `<dir>/NN/input.txt` and `<dir>/NN/puzzle.md`, so that they are never re-fetched.
+/// A correct answer drops the cached description, as solving part one reveals part two.
+/// A part solved elsewhere, e.g. on the website, is only revealed by refreshing the cache (see [`Cache::refresh`]).
+pub struct Cache<C> {
+    dir: PathBuf,
+    inner: C,
+    refresh: bool,
+}
+
+impl<C: AocClient> Cache<C> {
+    pub fn new(dir: impl Into<PathBuf>, inner: C) -> Self {
+        Self {
+            dir: dir.into(),
+            inner,
+            refresh: false,
+        }
+    }
+
+    /// If set, puzzles are fetched again and the cached ones are replaced.
+    #[must_use]
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    fn input_path(&self, day: Day) -> PathBuf {
+        self.dir.join(day.to_string()).join("input.txt")
+    }
+
+    fn puzzle_path(&self, day: Day) -> PathBuf {
+        self.dir.join(day.to_string()).join("puzzle.md")
+    }
+}
+
+impl<C: AocClient> AocClient for Cache<C> {
+    fn fetch(&self, day: Day) -> Result<Puzzle, AocClientError> {
+        let (input_path, puzzle_path) = (self.input_path(day), self.puzzle_path(day));
+
+        if !self.refresh && input_path.exists() && puzzle_path.exists() {
+            return Ok(Puzzle {
+                input: read_file(&input_path)?,
+                description: read_file(&puzzle_path)?,
+            });
+        }
+
+        let puzzle = self.inner.fetch(day)?;
+        write_file(&input_path, &puzzle.input)?;
+        write_file(&puzzle_path, &puzzle.description)?;
+        Ok(puzzle)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, AocClientError> {
+        let verdict = self.inner.submit(day, part, answer)?;
+
+        if verdict == SubmitVerdict::Correct {
+            let puzzle_path = self.puzzle_path(day);
+            match fs::remove_file(&puzzle_path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    return Err(AocClientError::Io(puzzle_path, e))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(verdict)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A local stand-in for adventofcode.com, e.g. for testing `download`, `read` and `--submit` offline.
+/// Serves `<dir>/NN/input.txt` and `<dir>/NN/puzzle.md`, and checks submissions against `<dir>/NN/answer-<part>.txt`.
+/// Every request is appended to `<dir>/requests.log`.
+pub struct StubServer {
+    dir: PathBuf,
+}
+
+impl StubServer {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn log(&self, request: &str) -> Result<(), AocClientError> {
+        use std::io::Write;
+
+        let path = self.dir.join("requests.log");
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{request}"))
+            .map_err(|e| AocClientError::Io(path, e))
+    }
+}
+
+impl AocClient for StubServer {
+    fn fetch(&self, day: Day) -> Result<Puzzle, AocClientError> {
+        self.log(&format!("fetch {day}"))?;
+
+        let day_dir = self.dir.join(day.to_string());
+        if !day_dir.exists() {
+            return Err(AocClientError::Stub(format!("no puzzle for day {day}")));
+        }
+
+        Ok(Puzzle {
+            input: read_file(&day_dir.join("input.txt"))?,
+            description: read_file(&day_dir.join("puzzle.md"))?,
+        })
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, AocClientError> {
+        self.log(&format!("submit {day} {part} {answer}"))?;
+
+        let answer_path = self
+            .dir
+            .join(day.to_string())
+            .join(format!("answer-{part}.txt"));
+        let Ok(expected) = fs::read_to_string(answer_path) else {
+            return Ok(SubmitVerdict::WrongLevel);
+        };
+
+        let expected = expected.trim();
+        let verdict = if answer == expected {
+            SubmitVerdict::Correct
+        } else {
+            let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+                (Ok(answer), Ok(expected)) if answer > expected => Some(Hint::TooHigh),
+                (Ok(answer), Ok(expected)) if answer < expected => Some(Hint::TooLow),
+                _ => None,
+            };
+            SubmitVerdict::Incorrect(hint)
+        };
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::Cell, env, fs, path::PathBuf, process};
+
+    use super::{
+        download, read, AocClient, AocClientError, Cache, Puzzle, StubServer, SubmitVerdict,
+    };
+    use crate::{day, template::answers::Hint, template::Day};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-cli-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn stub_server(name: &str) -> PathBuf {
+        let dir = temp_dir(name);
+        fs::create_dir_all(dir.join("01")).unwrap();
+        fs::write(dir.join("01/input.txt"), "1\n2\n").unwrap();
+        fs::write(dir.join("01/puzzle.md"), "--- Day 1 ---").unwrap();
+        fs::write(dir.join("01/answer-1.txt"), "42\n").unwrap();
+        dir
+    }
+
+    /// Counts fetches, so that tests can tell whether the cache was hit.
+    struct Counting(Cell<usize>);
+
+    impl AocClient for Counting {
+        fn fetch(&self, _: Day) -> Result<Puzzle, AocClientError> {
+            self.0.set(self.0.get() + 1);
+            Ok(Puzzle {
+                input: "input".into(),
+                description: format!("fetch #{}", self.0.get()),
+            })
+        }
+
+        fn submit(&self, _: Day, _: u8, _: &str) -> Result<SubmitVerdict, AocClientError> {
+            Ok(SubmitVerdict::Correct)
+        }
+    }
+
+    #[test]
+    fn stub_server_serves_puzzles_and_checks_answers() {
+        let dir = stub_server("stub");
+        let server = StubServer::new(&dir);
+
+        let puzzle = server.fetch(day!(1)).unwrap();
+        assert_eq!(puzzle.input, "1\n2\n");
+        assert_eq!(puzzle.description, "--- Day 1 ---");
+        assert!(matches!(
+            server.fetch(day!(2)),
+            Err(AocClientError::Stub(_))
+        ));
+
+        let submit = |answer| server.submit(day!(1), 1, answer).unwrap();
+        assert_eq!(submit("42"), SubmitVerdict::Correct);
+        assert_eq!(submit("43"), SubmitVerdict::Incorrect(Some(Hint::TooHigh)));
+        assert_eq!(submit("41"), SubmitVerdict::Incorrect(Some(Hint::TooLow)));
+        assert_eq!(submit("abc"), SubmitVerdict::Incorrect(None));
+        assert_eq!(
+            server.submit(day!(1), 2, "1").unwrap(),
+            SubmitVerdict::WrongLevel
+        );
+
+        let log = fs::read_to_string(dir.join("requests.log")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(log.lines().next(), Some("fetch 01"));
+        assert_eq!(log.lines().count(), 7);
+    }
+
+    #[test]
+    fn cache_fetches_once() {
+        let dir = temp_dir("cache");
+        let cache = Cache::new(&dir, Counting(Cell::new(0)));
+
+        assert_eq!(cache.fetch(day!(1)).unwrap().description, "fetch #1");
+        assert_eq!(cache.fetch(day!(1)).unwrap().description, "fetch #1");
+        assert_eq!(cache.inner.0.get(), 1);
+
+        // a correct answer reveals the next part of the description.
+        cache.submit(day!(1), 1, "42").unwrap();
+        assert_eq!(cache.fetch(day!(1)).unwrap().description, "fetch #2");
+
+        // a part solved elsewhere is only revealed by refreshing.
+        let cache = cache.refresh(true);
+        assert_eq!(cache.fetch(day!(1)).unwrap().description, "fetch #3");
+        let cache = cache.refresh(false);
+        assert_eq!(cache.fetch(day!(1)).unwrap().description, "fetch #3");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn downloads_and_reads_offline() {
+        let server_dir = stub_server("download");
+        let data_dir = temp_dir("download-data");
+        let client = Cache::new(data_dir.join("cache"), StubServer::new(&server_dir));
+
+        download(&client, day!(1), &data_dir).unwrap();
+        assert_eq!(read(&client, day!(1)).unwrap(), "--- Day 1 ---");
+
+        let input = fs::read_to_string(data_dir.join("inputs/01.txt")).unwrap();
+        let puzzle = fs::read_to_string(data_dir.join("puzzles/01.md")).unwrap();
+        let log = fs::read_to_string(server_dir.join("requests.log")).unwrap();
+        fs::remove_dir_all(&server_dir).unwrap();
+        fs::remove_dir_all(&data_dir).unwrap();
+
+        assert_eq!(input, "1\n2\n");
+        assert_eq!(puzzle, "--- Day 1 ---");
+        assert_eq!(log, "fetch 01\n");
+    }
+
+    #[test]
+    fn classifies_submit_output() {
+        assert_eq!(
+            SubmitVerdict::from_output("That's the right answer! You are one gold star closer."),
+            SubmitVerdict::Correct
+        );
+        assert_eq!(
+            SubmitVerdict::from_output(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            ),
+            SubmitVerdict::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            SubmitVerdict::from_output("That's not the right answer; your answer is too low."),
+            SubmitVerdict::Incorrect(Some(Hint::TooLow))
+        );
+        assert_eq!(
+            SubmitVerdict::from_output("That's not the right answer."),
+            SubmitVerdict::Incorrect(None)
+        );
+        assert_eq!(
+            SubmitVerdict::from_output("You gave an answer too recently; you have to wait."),
+            SubmitVerdict::TooRecent
+        );
+        assert_eq!(
+            SubmitVerdict::from_output(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            SubmitVerdict::WrongLevel
+        );
+        assert_eq!(SubmitVerdict::from_output(""), SubmitVerdict::Unknown);
+    }
+}
@@ -0,0 +1,362 @@
+//! The command line of the year binaries, shared by all year crates.
+//! The `main.rs` of a year crate only includes its registry of solutions, generated by the `aoc_registry` crate, and calls [`run`].
+use std::{process, time::Duration};
+
+use crate::template::{
+    commands::{
+        all, download, extract, read, readme,
+        scaffold::{self, DEFAULT_TEMPLATE},
+        solve,
+        time::{self, DEFAULT_THRESHOLD},
+        verify,
+        watch::{self, WatchTargets},
+    },
+    output::OutputFormat,
+    runner::{BenchConfig, Solution},
+    Day, InputSource, Params,
+};
+
+#[cfg(feature = "today")]
+use crate::template::last_day;
+
+enum AppArguments {
+    Download {
+        day: Day,
+        refresh: bool,
+    },
+    Read {
+        day: Day,
+        refresh: bool,
+    },
+    Extract {
+        day: Day,
+        block: Option<usize>,
+        list: bool,
+        overwrite: bool,
+    },
+    Scaffold {
+        day: Day,
+        download: bool,
+        overwrite: bool,
+        template: String,
+    },
+    Solve {
+        day: Day,
+        release: bool,
+        dhat: bool,
+        submit: Option<u8>,
+        params: Params,
+        input: InputSource,
+        timeout: Option<Duration>,
+        variant: Option<String>,
+        compare: bool,
+        format: OutputFormat,
+    },
+    All {
+        release: bool,
+        timeout: Option<Duration>,
+        jobs: usize,
+        format: OutputFormat,
+    },
+    Time {
+        all: bool,
+        day: Option<Day>,
+        store: bool,
+        bench: BenchConfig,
+        compare: Option<f64>,
+        timeout: Option<Duration>,
+        memory: bool,
+        format: OutputFormat,
+    },
+    Verify {
+        day: Option<Day>,
+        store: bool,
+        timeout: Option<Duration>,
+        jobs: usize,
+    },
+    Watch {
+        day: Day,
+        release: bool,
+        targets: WatchTargets,
+    },
+    Readme,
+    #[cfg(feature = "today")]
+    Today,
+}
+
+fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    let mut args = pico_args::Arguments::from_env();
+
+    let subcommand = args.subcommand()?;
+
+    // `--format` is accepted by all commands that print results, i.e. `solve`, `all` and `time`.
+    let format_arg: Option<OutputFormat> = args.opt_value_from_str("--format")?;
+    let format = format_arg.unwrap_or_default();
+
+    let app_args = match subcommand.as_deref() {
+        Some("all") => AppArguments::All {
+            release: args.contains("--release"),
+            timeout: parse_timeout(&mut args)?,
+            jobs: parse_jobs(&mut args)?,
+            format,
+        },
+        Some("time") => {
+            let all = args.contains("--all");
+            let store = args.contains("--store");
+            let budget: Option<u64> = args.opt_value_from_str("--budget")?;
+            let iterations: Option<u64> = args.opt_value_from_str("--iterations")?;
+            if iterations == Some(0) {
+                return Err("`--iterations` must be at least 1.".into());
+            }
+            let compare = args.contains("--compare");
+            let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+            let memory = args.contains("--memory");
+
+            let bench = BenchConfig {
+                budget: budget.map_or(BenchConfig::default().budget, Duration::from_millis),
+                iterations,
+            };
+
+            AppArguments::Time {
+                all,
+                day: args.opt_free_from_str()?,
+                store,
+                bench,
+                compare: compare.then(|| threshold.unwrap_or(DEFAULT_THRESHOLD)),
+                timeout: parse_timeout(&mut args)?,
+                memory,
+                format,
+            }
+        }
+        Some("verify") => {
+            let store = args.contains("--store");
+
+            let timeout = parse_timeout(&mut args)?;
+            let jobs = parse_jobs(&mut args)?;
+
+            AppArguments::Verify {
+                day: args.opt_free_from_str()?,
+                store,
+                timeout,
+                jobs,
+            }
+        }
+        Some("watch") => {
+            let test = args.contains("--test");
+            let run = args.contains("--run");
+
+            AppArguments::Watch {
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
+                // without either flag, both tests and the solution are run.
+                targets: WatchTargets {
+                    test: test || !run,
+                    run: run || !test,
+                },
+            }
+        }
+        Some("readme") => AppArguments::Readme,
+        Some("download") => AppArguments::Download {
+            day: args.free_from_str()?,
+            refresh: args.contains("--refresh"),
+        },
+        Some("read") => AppArguments::Read {
+            day: args.free_from_str()?,
+            refresh: args.contains("--refresh"),
+        },
+        Some("extract") => AppArguments::Extract {
+            day: args.free_from_str()?,
+            block: args.opt_value_from_str("--block")?,
+            list: args.contains("--list"),
+            overwrite: args.contains("--overwrite"),
+        },
+        Some("scaffold") => AppArguments::Scaffold {
+            day: args.free_from_str()?,
+            download: args.contains("--download"),
+            overwrite: args.contains("--overwrite"),
+            template: args
+                .opt_value_from_str("--template")?
+                .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string()),
+        },
+        Some("solve") => AppArguments::Solve {
+            day: args.free_from_str()?,
+            release: args.contains("--release"),
+            submit: args.opt_value_from_str("--submit")?,
+            dhat: args.contains("--dhat"),
+            params: args
+                .values_from_fn("--param", Params::parse_pair)?
+                .into_iter()
+                .collect(),
+            input: parse_input(&mut args)?,
+            timeout: parse_timeout(&mut args)?,
+            variant: args.opt_value_from_str("--variant")?,
+            compare: args.contains("--compare"),
+            format,
+        },
+        #[cfg(feature = "today")]
+        Some("today") => AppArguments::Today,
+        Some(x) => {
+            eprintln!("Unknown command: {x}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("No command specified.");
+            process::exit(1);
+        }
+    };
+
+    let prints_results = matches!(
+        app_args,
+        AppArguments::Solve { .. } | AppArguments::All { .. } | AppArguments::Time { .. }
+    );
+    if format_arg.is_some() && !prints_results {
+        return Err("`--format` is only supported by `solve`, `all` and `time`.".into());
+    }
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    }
+
+    Ok(app_args)
+}
+
+/// Parse `--timeout <ms>`, the wall-clock time after which a part is reported as timed out.
+fn parse_timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, pico_args::Error> {
+    Ok(args
+        .opt_value_from_str("--timeout")?
+        .map(Duration::from_millis))
+}
+
+/// Parse `--jobs <N>`, the number of days to run concurrently.
+fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+    Ok(args.opt_value_from_str("--jobs")?.unwrap_or(1))
+}
+
+/// Parse `--input <path | ->` or `--example [suffix]`, where the suffix of the example is optional.
+fn parse_input(args: &mut pico_args::Arguments) -> Result<InputSource, Box<dyn std::error::Error>> {
+    let input: Option<String> = args.opt_value_from_str("--input")?;
+    // a following flag is not taken as the suffix, e.g. in `--example --release`.
+    let suffix = |s: &str| {
+        if s.starts_with('-') {
+            Err("expected an example suffix")
+        } else {
+            Ok(s.to_string())
+        }
+    };
+    let example = match args.opt_value_from_fn("--example", suffix) {
+        Ok(Some(n)) => Some(Some(n)),
+        Ok(None) => None,
+        Err(_) => args.contains("--example").then_some(None),
+    };
+
+    match (input, example) {
+        (Some(_), Some(_)) => Err("`--input` and `--example` are mutually exclusive.".into()),
+        (Some(path), None) if path == "-" => Ok(InputSource::Stdin),
+        (Some(path), None) => Ok(InputSource::File(path.into())),
+        (None, Some(n)) => Ok(InputSource::Example(n)),
+        (None, None) => Ok(InputSource::Puzzle),
+    }
+}
+
+/// Parse the command line arguments and run the command, with `solutions` linked in-process.
+pub fn run(solutions: &[Solution]) {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+        Ok(args) => match args {
+            AppArguments::All {
+                release,
+                timeout,
+                jobs,
+                format,
+            } => all::handle(solutions, release, timeout, jobs, format),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                bench,
+                compare,
+                timeout,
+                memory,
+                format,
+            } => time::handle(
+                solutions, day, all, store, bench, compare, timeout, memory, format,
+            ),
+            AppArguments::Verify {
+                day,
+                store,
+                timeout,
+                jobs,
+            } => verify::handle(solutions, day, store, timeout, jobs),
+            AppArguments::Watch {
+                day,
+                release,
+                targets,
+            } => watch::handle(day, release, targets),
+            AppArguments::Readme => readme::handle(),
+            AppArguments::Download { day, refresh } => download::handle(day, refresh),
+            AppArguments::Read { day, refresh } => read::handle(day, refresh),
+            AppArguments::Extract {
+                day,
+                block,
+                list,
+                overwrite,
+            } => extract::handle(day, block, list, overwrite),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                template,
+            } => {
+                scaffold::handle(day, overwrite, &template);
+                if download {
+                    download::handle(day, false);
+                }
+            }
+            AppArguments::Solve {
+                day,
+                release,
+                dhat,
+                submit,
+                params,
+                input,
+                timeout,
+                variant,
+                compare,
+                format,
+            } => solve::handle(
+                day,
+                release,
+                dhat,
+                submit,
+                &params,
+                &input,
+                timeout,
+                variant.as_deref(),
+                compare,
+                format,
+            ),
+            #[cfg(feature = "today")]
+            AppArguments::Today => {
+                match Day::today() {
+                    Some(day) => {
+                        scaffold::handle(day, false, DEFAULT_TEMPLATE);
+                        download::handle(day, false);
+                        read::handle(day, false)
+                    }
+                    None => {
+                        eprintln!(
+                            "`today` command can only be run between the 1st and \
+                            the {}th of december. Please use `scaffold` with a specific day.",
+                            last_day()
+                        );
+                        process::exit(1)
+                    }
+                };
+            }
+        },
+    };
+}
@@ -1,5 +1,23 @@
+use std::time::Duration;
+
+use crate::template::output::OutputFormat;
+use crate::template::runner::{RunOptions, Solution};
 use crate::template::{all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    solutions: &[Solution],
+    is_release: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+    format: OutputFormat,
+) {
+    let options = RunOptions {
+        timeout,
+        jobs,
+        format,
+        ..RunOptions::default()
+    };
+
+    format.print_header();
+    run_multi(solutions, &all_days().collect(), is_release, &options);
 }
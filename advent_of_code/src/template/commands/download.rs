@@ -1,14 +1,14 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_client, Day};
+use std::{path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+/// `refresh` fetches the puzzle again, instead of using the cached one.
+pub fn handle(day: Day, refresh: bool) {
+    if let Err(e) = aoc_client::download(
+        &aoc_client::client().refresh(refresh),
+        day,
+        Path::new("data"),
+    ) {
+        eprintln!("failed to download puzzle: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,393 @@
+//! Extracts the example of a day from its downloaded puzzle description (`data/puzzles/NN.md`), instead of copying it by hand.
+//!
+//! The primary example is guessed to be the first code block that is introduced as an example.
+//! The expected answer of a part is guessed to be the last highlighted code (`<code><em>`) of its section,
+//! which is how the puzzles state the answer for the example.
+//! The example is written to `data/examples/NN.txt` with the answers in its header (see [`crate::template::examples`]),
+//! and the answers are filled into the tests of the scaffolded module, if they are still unset.
+//! A part two that introduces an example of its own gets `data/examples/NN-2.txt`.
+use std::{fs, path::Path, process};
+
+use crate::template::{examples::Example, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static CODE_FENCE: &str = "```";
+static PART_TWO_HEADING: &str = "## --- Part Two";
+static UNSET_ASSERTION: &str = "assert_eq!(result, None);";
+
+/// A code block of the puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CodeBlock {
+    /// The part whose section contains the block.
+    part: u8,
+    /// The paragraph right before the block.
+    intro: String,
+    contents: String,
+}
+
+impl CodeBlock {
+    fn is_example(&self) -> bool {
+        self.intro.to_lowercase().contains("example")
+    }
+}
+
+/// What is extracted from a puzzle description.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Puzzle {
+    blocks: Vec<CodeBlock>,
+    /// The last highlighted code of the section of each part.
+    answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Parse the markdown written by [`crate::template::aoc_client::markdown::from_html`].
+    fn parse(markdown: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        let mut part = 1;
+        let mut paragraph = String::new();
+        let mut block: Option<String> = None;
+        let mut previous_blank = true;
+
+        for line in markdown.lines() {
+            if line.trim_end() == CODE_FENCE {
+                match block.take() {
+                    Some(contents) => puzzle.blocks.push(CodeBlock {
+                        part,
+                        intro: paragraph.trim().to_string(),
+                        contents,
+                    }),
+                    None => block = Some(String::new()),
+                }
+                continue;
+            }
+
+            if let Some(contents) = block.as_mut() {
+                contents.push_str(line);
+                contents.push('\n');
+                continue;
+            }
+
+            if line.starts_with(PART_TWO_HEADING) {
+                part = 2;
+            }
+
+            if line.trim().is_empty() {
+                previous_blank = true;
+                continue;
+            }
+
+            // the intro of a block is the last paragraph before it.
+            if previous_blank {
+                paragraph.clear();
+            }
+            paragraph.push_str(line);
+            paragraph.push('\n');
+            previous_blank = false;
+
+            if let Some(answer) = highlighted(line).pop() {
+                puzzle.answers[usize::from(part - 1)] = Some(answer);
+            }
+        }
+
+        puzzle
+    }
+
+    /// The index of the block that is guessed to be the example of `part`, see the module docs.
+    /// Part one falls back to the first block, part two only has an example if its section introduces one.
+    fn example(&self, part: u8) -> Option<usize> {
+        let introduced = self
+            .blocks
+            .iter()
+            .position(|block| block.part == part && block.is_example());
+
+        match part {
+            1 => introduced.or((!self.blocks.is_empty()).then_some(0)),
+            _ => introduced,
+        }
+    }
+}
+
+/// The highlighted code of a line, i.e. `` `*x*` `` or `` *`x`* `` in markdown.
+fn highlighted(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    loop {
+        let open = [("`*", "*`"), ("*`", "`*")]
+            .into_iter()
+            .filter_map(|(open, close)| Some((rest.find(open)?, open, close)))
+            .min_by_key(|(index, _, _)| *index);
+        let Some((start, open, close)) = open else {
+            break;
+        };
+
+        let value_start = start + open.len();
+        let Some(end) = rest[value_start..].find(close).map(|i| value_start + i) else {
+            break;
+        };
+
+        let value = &rest[value_start..end];
+        if !value.is_empty() && !value.contains('`') {
+            values.push(value.to_string());
+        }
+        rest = &rest[end + close.len()..];
+    }
+
+    values
+}
+
+/// Fill the answer into the test of `part` in `module`, if its assertion is still unset.
+/// Answers that are not integers are left out, as the tests of the templates expect an `Option<u64>`.
+/// If part two has an example of its own, its test is changed to read `NN-2.txt`.
+fn fill_test(module: &str, part: u8, answer: Option<&str>, own_example: bool) -> Option<String> {
+    let name = if part == 1 { "one" } else { "two" };
+    let start = module.find(&format!("fn test_part_{name}()"))?;
+    let end = module[start..]
+        .find("\n    }")
+        .map_or(module.len(), |i| start + i);
+
+    let test = &module[start..end];
+    if !test.contains(UNSET_ASSERTION) {
+        return None;
+    }
+
+    let mut filled = test.to_string();
+    if let Some(answer) = answer.filter(|answer| answer.parse::<i64>().is_ok()) {
+        filled = filled.replace(
+            UNSET_ASSERTION,
+            &format!("assert_eq!(result, Some({answer}));"),
+        );
+    }
+    if own_example {
+        filled = filled.replace(
+            "read_file(\"examples\", DAY)",
+            "read_file_part(\"examples\", DAY, 2)",
+        );
+    }
+
+    (filled != test).then(|| format!("{}{filled}{}", &module[..start], &module[end..]))
+}
+
+fn print_blocks(puzzle: &Puzzle, examples: &[Option<usize>; 2]) {
+    for (index, block) in puzzle.blocks.iter().enumerate() {
+        let lines = block.contents.lines().count();
+        let preview = block.contents.lines().next().unwrap_or_default();
+        let marker = match examples.iter().position(|&e| e == Some(index)) {
+            Some(part) => format!(" {ANSI_BOLD}← example of part {}{ANSI_RESET}", part + 1),
+            None => String::new(),
+        };
+
+        println!(
+            "{}. part {}, {lines} line{}: {ANSI_ITALIC}{preview}{ANSI_RESET}{marker}",
+            index + 1,
+            block.part,
+            if lines == 1 { "" } else { "s" }
+        );
+    }
+
+    for (part, answer) in puzzle.answers.iter().enumerate() {
+        match answer {
+            Some(answer) => println!("Part {}: {ANSI_BOLD}{answer}{ANSI_RESET}", part + 1),
+            None => println!("Part {}: no highlighted answer found", part + 1),
+        }
+    }
+}
+
+/// Write an example file, unless it already has contents and `overwrite` is not set.
+fn write_example(path: &str, example: &Example, overwrite: bool) {
+    let exists = fs::read_to_string(path).is_ok_and(|contents| !contents.trim().is_empty());
+    if exists && !overwrite {
+        eprintln!("Example file \"{path}\" is not empty, use `--overwrite` to replace it.");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(path, example.contents()) {
+        eprintln!("Failed to write example file \"{path}\": {e}");
+        process::exit(1);
+    }
+
+    println!("Wrote example to \"{path}\"");
+}
+
+/// `block` selects the example of part one by its number in the list, instead of guessing it.
+pub fn handle(day: Day, block: Option<usize>, list: bool, overwrite: bool) {
+    let puzzle_path = format!("data/puzzles/{day}.md");
+    let module_path = format!("src/bin/{day}.rs");
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle \"{puzzle_path}\": {e}. Run `cargo download {day}` first."
+            );
+            process::exit(1);
+        }
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+    if puzzle.blocks.is_empty() {
+        eprintln!("No code blocks found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    let part_one = match block {
+        Some(n) if (1..=puzzle.blocks.len()).contains(&n) => Some(n - 1),
+        Some(n) => {
+            eprintln!(
+                "Block {n} does not exist, expected a number from 1 to {}.",
+                puzzle.blocks.len()
+            );
+            process::exit(1);
+        }
+        None => puzzle.example(1),
+    };
+    // a part two without an example of its own, or that repeats it, is checked against the example of part one.
+    let part_two = puzzle.example(2).filter(|&index| {
+        part_one.is_none_or(|one| puzzle.blocks[one].contents != puzzle.blocks[index].contents)
+    });
+    let examples = [part_one, part_two];
+
+    print_blocks(&puzzle, &examples);
+    if list {
+        return;
+    }
+
+    let [answer_1, answer_2] = puzzle.answers.clone();
+    let mut example = Example {
+        name: format!("{day}.txt"),
+        part_1: answer_1.clone(),
+        part_2: if part_two.is_none() {
+            answer_2.clone()
+        } else {
+            None
+        },
+        input: puzzle.blocks[part_one.unwrap_or_default()].contents.clone(),
+        ..Example::default()
+    };
+
+    println!("---");
+    write_example(&format!("data/examples/{day}.txt"), &example, overwrite);
+
+    if let Some(index) = part_two {
+        example = Example {
+            name: format!("{day}-2.txt"),
+            part_2: answer_2.clone(),
+            input: puzzle.blocks[index].contents.clone(),
+            ..Example::default()
+        };
+        write_example(&format!("data/examples/{day}-2.txt"), &example, overwrite);
+    }
+
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let mut filled = vec![];
+    for (part, answer, own_example) in [
+        (1, answer_1.as_deref(), false),
+        (2, answer_2.as_deref(), part_two.is_some()),
+    ] {
+        if let Some(updated) = fill_test(&module, part, answer, own_example) {
+            module = updated;
+            filled.push(part.to_string());
+        }
+    }
+
+    if filled.is_empty() {
+        return;
+    }
+
+    match fs::write(Path::new(&module_path), module) {
+        Ok(()) => println!(
+            "Filled the expected answers into the test of part {} in \"{module_path}\"",
+            filled.join(" and ")
+        ),
+        Err(e) => {
+            eprintln!("Failed to write module file \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_test, highlighted, Puzzle};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+There is a list of location IDs:
+
+```
+1 2
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+The total distance is `*2*`, the *smallest* pair is *`3`*, the answer is `*11*`.
+
+## --- Part Two ---
+
+Your puzzle answer was `1234`.
+
+Here are the same example lists again:
+
+```
+3   4
+```
+
+The similarity score is `*31*`.
+";
+
+    #[test]
+    fn finds_highlighted_code() {
+        assert_eq!(
+            highlighted("is `*2*`, *not* *`3`* or `4`, `*a*b*` and `*11*`!"),
+            vec!["2", "3", "a*b", "11"]
+        );
+        assert!(highlighted("*emphasis* only and `code` only").is_empty());
+    }
+
+    #[test]
+    fn parses_puzzles() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(puzzle.blocks.len(), 3);
+        assert_eq!(puzzle.blocks[1].contents, "3   4\n4   3\n");
+        assert_eq!(puzzle.blocks[1].intro, "For example:");
+        assert_eq!(puzzle.blocks[2].part, 2);
+        assert_eq!(puzzle.answers, [Some("11".into()), Some("31".into())]);
+
+        assert_eq!(puzzle.example(1), Some(1));
+        assert_eq!(puzzle.example(2), Some(2));
+        assert_eq!(Puzzle::parse("```\nabc\n```\n").example(1), Some(0));
+        assert_eq!(Puzzle::parse("No blocks.").example(1), None);
+    }
+
+    #[test]
+    fn fills_tests() {
+        let module = "    fn test_part_one() {
+        let result = part_one(&read_file(\"examples\", DAY).unwrap());
+        assert_eq!(result, None);
+    }
+
+    fn test_part_two() {
+        let result = part_two(&read_file(\"examples\", DAY).unwrap());
+        assert_eq!(result, None);
+    }
+";
+
+        let filled = fill_test(module, 1, Some("11"), false).unwrap();
+        assert!(filled.contains("assert_eq!(result, Some(11));"));
+        assert_eq!(filled.matches("assert_eq!(result, None);").count(), 1);
+        assert_eq!(fill_test(&filled, 1, Some("12"), false), None);
+
+        let filled = fill_test(&filled, 2, Some("abc"), true).unwrap();
+        assert!(filled.contains("part_two(&read_file_part(\"examples\", DAY, 2)"));
+        assert!(filled.contains("part_one(&read_file(\"examples\", DAY)"));
+        assert_eq!(fill_test(module, 2, None, false), None);
+    }
+}
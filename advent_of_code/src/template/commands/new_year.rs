@@ -0,0 +1,214 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::readme;
+
+/// Files of the template crate that contain the `{Year}` placeholder.
+const TEMPLATED_FILES: [&str; 3] = ["Cargo.toml", "README.md", ".cargo/config.toml"];
+
+/// Paths of the template crate that are not copied, as they are created by building or running it in place.
+const SKIPPED_PATHS: [&str; 3] = ["target", "Cargo.lock", "data/cache"];
+
+pub fn handle(workspace_root: &Path, year: u16) {
+    let crate_name = format!("aoc{year}");
+    let crate_path = workspace_root.join(&crate_name);
+    let manifest_path = workspace_root.join("Cargo.toml");
+    let readme_path = workspace_root.join("README.md");
+
+    if crate_path.exists() {
+        eprintln!("Failed to create year crate: \"{crate_name}\" already exists.");
+        process::exit(1);
+    }
+
+    // the workspace files are updated in memory first, so that a failure leaves nothing behind.
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|manifest| register_member(&manifest, &crate_name));
+    let manifest = match manifest {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Failed to register \"{crate_name}\" in the workspace: {e}");
+            process::exit(1);
+        }
+    };
+
+    let readme = fs::read_to_string(&readme_path)
+        .map_err(readme::Error::from)
+        .and_then(|readme| readme::add_year(&readme, year));
+    let readme = match readme {
+        Ok(readme) => readme,
+        Err(e) => {
+            eprintln!("Failed to update README: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = create_crate(&workspace_root.join("template"), &crate_path, year) {
+        eprintln!("Failed to create year crate: {e}");
+        remove_crate(&crate_path);
+        process::exit(1);
+    }
+
+    println!("Created year crate \"{crate_name}\"");
+
+    if let Err(e) = fs::write(&manifest_path, manifest) {
+        eprintln!("Failed to register \"{crate_name}\" in the workspace: {e}");
+        remove_crate(&crate_path);
+        process::exit(1);
+    }
+
+    println!("Added \"{crate_name}\" to the workspace members");
+
+    match fs::write(&readme_path, readme) {
+        Ok(()) => println!("Added {year} to the README years table"),
+        // the crate is complete and registered at this point, only the table is missing.
+        Err(e) => eprintln!("Failed to update README: {e}"),
+    }
+
+    println!("---");
+    println!("🎄 Run `cd {crate_name} && cargo scaffold 1` to get started.");
+}
+
+/// Remove a partially created crate.
+fn remove_crate(crate_path: &Path) {
+    match fs::remove_dir_all(crate_path) {
+        Ok(()) => eprintln!("Removed \"{}\"", crate_path.display()),
+        Err(e) => eprintln!("Failed to remove \"{}\": {e}", crate_path.display()),
+    }
+}
+
+fn create_crate(template_path: &Path, crate_path: &Path, year: u16) -> io::Result<()> {
+    copy_dir(template_path, crate_path, Path::new(""))?;
+
+    for file in TEMPLATED_FILES {
+        let path = crate_path.join(file);
+        let contents = fs::read_to_string(&path)?;
+        fs::write(&path, contents.replace("{Year}", &year.to_string()))?;
+    }
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        fs::create_dir_all(crate_path.join("data").join(folder))?;
+    }
+
+    Ok(())
+}
+
+/// Copy the contents of `from` to `to`, except for [`SKIPPED_PATHS`].
+/// `relative` is the path of `from` within the template crate.
+fn copy_dir(from: &Path, to: &Path, relative: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        if SKIPPED_PATHS
+            .iter()
+            .any(|skipped| path == Path::new(skipped))
+        {
+            continue;
+        }
+
+        let target: PathBuf = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target, &path)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Add `member` to the `members` list of a workspace manifest, keeping the list sorted.
+fn register_member(manifest: &str, member: &str) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .map(|i| i + "members = [".len())
+        .ok_or("expected manifest to contain a `members` list.")?;
+    let end = manifest[start..]
+        .find(']')
+        .map(|i| start + i)
+        .ok_or("expected `members` list to be closed.")?;
+
+    let mut members: Vec<String> = manifest[start..end]
+        .split(',')
+        .map(|m| m.trim().trim_matches('"').to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+
+    if members.iter().any(|m| m == member) {
+        return Err(format!("\"{member}\" is already a workspace member."));
+    }
+
+    members.push(member.to_string());
+    members.sort_unstable();
+
+    let list: String = members.iter().map(|m| format!("\n    \"{m}\",")).collect();
+
+    Ok(format!(
+        "{}{list}\n{}",
+        &manifest[..start],
+        &manifest[end..]
+    ))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use super::{copy_dir, register_member};
+
+    #[test]
+    fn registers_members_in_order() {
+        let manifest = "[workspace]\nmembers = [\n    \"advent_of_code\",\n    \"aoc2015\",\n    \"aoc2024\",\n    \"aoc_utils\",\n]\nexclude = [\"template\"]\n";
+
+        assert_eq!(
+            register_member(manifest, "aoc2025").unwrap(),
+            "[workspace]\nmembers = [\n    \"advent_of_code\",\n    \"aoc2015\",\n    \"aoc2024\",\n    \"aoc2025\",\n    \"aoc_utils\",\n]\nexclude = [\"template\"]\n"
+        );
+    }
+
+    #[test]
+    fn rejects_existing_members() {
+        let manifest = "members = [\"aoc2024\"]\n";
+        assert!(register_member(manifest, "aoc2024").is_err());
+    }
+
+    #[test]
+    fn skips_build_artifacts() {
+        let root = env::temp_dir().join(format!("aoc-new-year-{}", process::id()));
+        let (from, to) = (root.join("template"), root.join("aoc2030"));
+        for dir in ["src/bin", "target/debug", "data/cache/01", "data/examples"] {
+            fs::create_dir_all(from.join(dir)).unwrap();
+        }
+        for file in [
+            "Cargo.toml",
+            "Cargo.lock",
+            "src/main.rs",
+            "target/debug/aoc",
+            "data/cache/01/input.txt",
+            "data/examples/.keep",
+        ] {
+            fs::write(from.join(file), "").unwrap();
+        }
+
+        copy_dir(&from, &to, Path::new("")).unwrap();
+
+        for file in [
+            "Cargo.toml",
+            "src/main.rs",
+            "src/bin",
+            "data/examples/.keep",
+        ] {
+            assert!(to.join(file).exists(), "{file} should be copied");
+        }
+        for file in ["Cargo.lock", "target", "data/cache"] {
+            assert!(!to.join(file).exists(), "{file} should be skipped");
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, readme, Day};
+
+pub fn handle() {
+    update_readme(&Timings::read_from_file());
+}
+
+/// Regenerate the README tables from the given timings, exiting if that fails.
+pub(crate) fn update_readme(timings: &Timings) {
+    let scaffolded: Vec<Day> = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    match readme::update(timings, &scaffolded) {
+        Ok(()) => println!("Updated README tables."),
+        Err(e) => {
+            eprintln!("failed to update README: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,13 +1,88 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
 use crate::template::Day;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// Name of the template that is used if none is selected.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Directory of the year crate with user-defined templates, one `<name>.txt` file per template.
+/// A user-defined template takes precedence over the built-in template of the same name.
+pub const TEMPLATES_DIR: &str = "./templates";
+
+macro_rules! builtin_template {
+    ($name:literal) => {
+        (
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/templates/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+/// Templates that ship with the template crate, for common puzzle styles.
+const BUILTIN_TEMPLATES: [(&str, &str); 5] = [
+    builtin_template!("default"),
+    builtin_template!("grid"),
+    builtin_template!("graph"),
+    builtin_template!("blocks"),
+    builtin_template!("regex"),
+];
+
+/// Names of the user-defined templates in `dir`, sorted.
+fn user_templates(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+
+    names.sort_unstable();
+    names
+}
+
+/// The contents of the template called `name`, looked up in `dir` first and in the built-in templates second.
+fn load_template(name: &str, dir: &Path) -> Result<String, String> {
+    let user_defined = user_templates(dir);
+
+    if user_defined.iter().any(|n| n == name) {
+        let path = dir.join(format!("{name}.txt"));
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read template \"{}\": {e}", path.display()));
+    }
+
+    if let Some((_, contents)) = BUILTIN_TEMPLATES.iter().find(|(n, _)| *n == name) {
+        return Ok((*contents).to_string());
+    }
+
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(n, _)| (*n).to_string())
+        .collect();
+    names.extend(
+        user_defined
+            .iter()
+            .map(|n| format!("{n} ({})", dir.join(format!("{n}.txt")).display())),
+    );
+
+    Err(format!(
+        "Unknown template `{name}`, expected one of: {}.",
+        names.join(", ")
+    ))
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -27,11 +102,20 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
+pub fn handle(day: Day, overwrite: bool, template: &str) {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
+    // checked first, so that an unknown template does not leave an empty module file behind.
+    let module_template = match load_template(template, Path::new(TEMPLATES_DIR)) {
+        Ok(module_template) => module_template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
         Err(e) => {
@@ -41,12 +125,15 @@ pub fn handle(day: Day, overwrite: bool) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
+        module_template
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template `{template}`",
+                &module_path
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -77,3 +164,40 @@ pub fn handle(day: Day, overwrite: bool) {
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{load_template, user_templates, BUILTIN_TEMPLATES};
+
+    #[test]
+    fn loads_builtin_templates() {
+        let dir = env::temp_dir().join("aoc-templates-missing");
+
+        for (name, _) in BUILTIN_TEMPLATES {
+            let template = load_template(name, &dir).unwrap();
+            assert!(template.contains("advent_of_code::solution!(%DAY_NUMBER%"));
+        }
+    }
+
+    #[test]
+    fn prefers_user_templates() {
+        let dir = env::temp_dir().join(format!("aoc-templates-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("grid.txt"), "my grid %DAY_NUMBER%").unwrap();
+        fs::write(dir.join("dp.txt"), "my dp %DAY_NUMBER%").unwrap();
+        fs::write(dir.join("notes.md"), "not a template").unwrap();
+
+        assert_eq!(user_templates(&dir), vec!["dp", "grid"]);
+        assert_eq!(load_template("grid", &dir).unwrap(), "my grid %DAY_NUMBER%");
+        assert_eq!(load_template("dp", &dir).unwrap(), "my dp %DAY_NUMBER%");
+
+        let error = load_template("tree", &dir).unwrap_err();
+        assert!(error.starts_with(
+            "Unknown template `tree`, expected one of: default, grid, graph, blocks, regex, dp ("
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
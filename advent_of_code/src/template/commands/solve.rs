@@ -1,8 +1,22 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::{output::OutputFormat, Day, InputSource, Params};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+// NOTE: mirrors the flags of `cargo solve`.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    params: &Params,
+    input: &InputSource,
+    timeout: Option<Duration>,
+    variant: Option<&str>,
+    compare: bool,
+    format: OutputFormat,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,6 +37,26 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(params.to_args());
+    cmd_args.extend(input.to_args());
+    cmd_args.extend(format.to_args());
+
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant.to_string());
+    }
+
+    if compare {
+        cmd_args.push("--compare".to_string());
+    }
+
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.as_millis().to_string());
+    }
+
+    format.print_header();
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +64,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // e.g. variants that disagree with `--compare`, so that scripts see the failure.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
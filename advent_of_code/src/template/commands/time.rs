@@ -1,16 +1,45 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::commands::readme;
+use crate::template::output::OutputFormat;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, Day};
+use crate::template::runner::{BenchConfig, RunOptions, Solution};
+use crate::template::timings::{format_bytes, TimingDelta, Timings};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Default regression threshold for `--compare`, in percent.
+pub const DEFAULT_THRESHOLD: f64 = 10_f64;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: BenchConfig,
+    compare: Option<f64>,
+    timeout: Option<Duration>,
+    memory: bool,
+    format: OutputFormat,
+) {
+    format.print_header();
+
+    if memory {
+        handle_memory(solutions, day, store, timeout, format);
+        return;
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // when comparing, only days with a baseline are of interest.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,10 +50,165 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        timed: true,
+        bench,
+        timeout,
+        format,
+        ..RunOptions::default()
+    };
+
+    let timings = run_multi(solutions, &days_to_run, true, &options)
+        .timings
+        .unwrap();
+
+    let regressions = compare.map_or(0, |threshold| {
+        let deltas = stored_timings.compare(&timings);
+        if format.is_text() {
+            print_comparison(&deltas, threshold)
+        } else {
+            count_regressions(&deltas, threshold)
+        }
+    });
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
+        readme::update_readme(&merged_timings);
+    }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Run every day once with the heap profiler and print the heap usage of each part.
+/// With `store`, the figures are merged into the stored timings, leaving the stored run times as they are.
+fn handle_memory(
+    solutions: &[Solution],
+    day: Option<Day>,
+    store: bool,
+    timeout: Option<Duration>,
+    format: OutputFormat,
+) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let options = RunOptions {
+        memory: true,
+        timeout,
+        format,
+        ..RunOptions::default()
+    };
+
+    let timings = run_multi(solutions, &days_to_run, true, &options)
+        .timings
+        .unwrap();
+
+    if format.is_text() {
+        print_memory(&timings);
+    }
+
+    if store {
+        let merged_timings = Timings::read_from_file().merge_memory(&timings);
+        merged_timings.store_file().unwrap();
+    }
+}
+
+fn print_memory(timings: &Timings) {
+    println!();
+    println!("{ANSI_BOLD}Memory{ANSI_RESET}");
+    println!("------");
+
+    println!(
+        "{:<4} {:<5} {:>10} {:>12} {:>10}",
+        "Day", "Part", "Peak", "Allocations", "Allocated"
+    );
+
+    for timing in &timings.data {
+        for (part, stats) in &timing.memory {
+            println!(
+                "{:<4} {:<5} {:>10} {:>12} {:>10}",
+                timing.day.to_string(),
+                if *part == 0 {
+                    "parse".to_string()
+                } else {
+                    part.to_string()
+                },
+                format_bytes(stats.peak_bytes),
+                stats.allocations,
+                format_bytes(stats.allocated_bytes),
+            );
+        }
+    }
+}
+
+fn count_regressions(deltas: &[TimingDelta], threshold: f64) -> usize {
+    deltas
+        .iter()
+        .filter(|delta| delta.change_percent() > threshold)
+        .count()
+}
+
+/// Print a table of deltas and return the number of parts that got slower by more than `threshold` percent.
+fn print_comparison(deltas: &[TimingDelta], threshold: f64) -> usize {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return 0;
+    }
+
+    println!(
+        "{:<4} {:<5} {:>10} {:>10} {:>11} {:>9}",
+        "Day", "Part", "Baseline", "Current", "Δ", "Δ%"
+    );
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let percent = delta.change_percent();
+
+        let color = if percent > threshold {
+            regressions += 1;
+            ANSI_RED
+        } else if percent < -threshold {
+            ANSI_GREEN
+        } else {
+            ANSI_RESET
+        };
+
+        let sign = if delta.change_nanos() < 0_f64 {
+            "-"
+        } else {
+            "+"
+        };
+
+        println!(
+            "{color}{:<4} {:<5} {:>10} {:>10} {:>11} {:>+8.1}%{ANSI_RESET}",
+            delta.day.to_string(),
+            if delta.part == 0 {
+                "parse".to_string()
+            } else {
+                delta.part.to_string()
+            },
+            format!("{:.1?}", nanos(delta.baseline_nanos)),
+            format!("{:.1?}", nanos(delta.current_nanos)),
+            format!("{sign}{:.1?}", nanos(delta.change_nanos().abs())),
+            percent,
+        );
     }
+
+    println!(
+        "{regressions} regression(s) above {threshold}% in {} part(s)",
+        deltas.len()
+    );
+
+    regressions
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos(x: f64) -> Duration {
+    Duration::from_nanos(x as u64)
 }
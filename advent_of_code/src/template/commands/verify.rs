@@ -0,0 +1,66 @@
+use std::collections::HashSet;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use crate::template::answers::Verdict;
+use crate::template::run_multi::{get_path_for_bin, run_multi};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(
+    solutions: &[Solution],
+    day: Option<Day>,
+    store: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    // only days that have been scaffolded can be verified.
+    let days_to_run = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    let options = RunOptions {
+        verify: true,
+        store_answers: store,
+        timeout,
+        jobs,
+        ..RunOptions::default()
+    };
+
+    let run = run_multi(solutions, &days_to_run, true, &options);
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = 0;
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for verification in &run.verifications {
+        match verification.verdict {
+            Verdict::Pass => passed += 1,
+            Verdict::Fail { .. } => failed += 1,
+            Verdict::Missing => missing += 1,
+        }
+
+        if verification.verdict != Verdict::Pass {
+            println!(
+                "Day {} Part {}: {}",
+                verification.day, verification.part, verification.verdict
+            );
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::examples::is_example_of;
+use crate::template::run_multi::{child_commands, get_path_for_bin, get_path_for_input};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// How often watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What to re-run when a watched file changes.
+#[derive(Clone, Copy, Debug)]
+pub struct WatchTargets {
+    /// Run `cargo test --bin <day>`.
+    pub test: bool,
+    /// Run the solution on the puzzle input.
+    pub run: bool,
+}
+
+/// Poll the solution, examples and input of a day, re-running tests and the solution on every change.
+/// Answers on the puzzle input are compared with the previous run.
+pub fn handle(day: Day, release: bool, targets: WatchTargets) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet, run `cargo scaffold {day}` first.");
+        std::process::exit(1);
+    }
+
+    let mut snapshot = take_snapshot(day);
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, Ctrl+C to stop){ANSI_RESET}");
+        println!("------");
+
+        if targets.test {
+            run_tests(day, release);
+        }
+
+        if targets.run {
+            match child_commands::run_solution(day, release, &RunOptions::default()) {
+                Ok(results) => {
+                    let answers = answers(&results);
+                    if let Some(previous) = &previous {
+                        print_changes(previous, &answers);
+                    }
+                    previous = Some(answers);
+                }
+                Err(e) => eprintln!("failed to run day {day}: {e:?}"),
+            }
+        }
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = take_snapshot(day);
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+
+        println!();
+    }
+}
+
+fn run_tests(day: Day, release: bool) {
+    let mut args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+    if release {
+        args.push("--release".to_string());
+    }
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => println!("Tests: {ANSI_GREEN}passed{ANSI_RESET}"),
+        Ok(_) => println!("Tests: {ANSI_RED}failed{ANSI_RESET}"),
+        Err(e) => eprintln!("failed to run tests for day {day}: {e}"),
+    }
+}
+
+/// Modification times of the files that trigger a re-run.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn take_snapshot(day: Day) -> Snapshot {
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        PathBuf::from(get_path_for_input(day)),
+    ];
+
+    // includes suffixed and named examples, e.g. `01-2.txt` or `01_small.txt`.
+    paths.extend(files_in("./data/examples", |name| is_example_of(name, day)));
+    // includes the modules of variants, e.g. `src/bin/10/softwaregore.rs`.
+    paths.extend(files_in(&format!("./src/bin/{day}"), |name| {
+        Path::new(name).extension().is_some_and(|ext| ext == "rs")
+    }));
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// The files in `dir` whose name matches `filter`, none if `dir` does not exist.
+fn files_in(dir: &str, filter: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_str().is_some_and(&filter))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of each part, the answer or the reason it has none.
+type Answers = BTreeMap<u8, String>;
+
+fn answers(results: &[PartResult]) -> Answers {
+    results
+        .iter()
+        // skip the parse step.
+        .filter(|result| result.part > 0)
+        .map(|result| {
+            let answer = match (&result.failure, &result.answer) {
+                (Some(failure), _) => format!("✖ {failure}"),
+                (None, Some(answer)) => answer.clone(),
+                (None, None) => "✖".to_string(),
+            };
+            (result.part, answer)
+        })
+        .collect()
+}
+
+/// Describe how the answers changed since the previous run, one line per part.
+fn changes(previous: &Answers, current: &Answers) -> Vec<String> {
+    let parts: BTreeSet<u8> = previous.keys().chain(current.keys()).copied().collect();
+
+    parts
+        .into_iter()
+        .map(|part| match (previous.get(&part), current.get(&part)) {
+            (Some(before), Some(after)) if before == after => {
+                format!("Part {part}: {ANSI_ITALIC}unchanged{ANSI_RESET}")
+            }
+            (Some(before), Some(after)) => {
+                format!(
+                    "Part {part}: {ANSI_RED}{before}{ANSI_RESET} → {ANSI_GREEN}{after}{ANSI_RESET}"
+                )
+            }
+            (None, Some(after)) => format!("Part {part}: → {ANSI_GREEN}{after}{ANSI_RESET}"),
+            (Some(before), None) => {
+                format!("Part {part}: {ANSI_RED}{before}{ANSI_RESET} → not run")
+            }
+            (None, None) => unreachable!(),
+        })
+        .collect()
+}
+
+fn print_changes(previous: &Answers, current: &Answers) {
+    println!();
+    println!("{ANSI_BOLD}Changes{ANSI_RESET}");
+    println!("------");
+    for line in changes(previous, current) {
+        println!("{line}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{changes, files_in, Answers};
+    use crate::{
+        day,
+        template::{examples::is_example_of, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET},
+    };
+
+    fn answers(answers: &[(u8, &str)]) -> Answers {
+        answers
+            .iter()
+            .map(|(p, a)| (*p, (*a).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn describes_changed_answers() {
+        let previous = answers(&[(1, "11"), (2, "✖")]);
+        let current = answers(&[(1, "11"), (2, "31")]);
+
+        assert_eq!(
+            changes(&previous, &current),
+            vec![
+                format!("Part 1: {ANSI_ITALIC}unchanged{ANSI_RESET}"),
+                format!("Part 2: {ANSI_RED}✖{ANSI_RESET} → {ANSI_GREEN}31{ANSI_RESET}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn describes_added_and_removed_parts() {
+        let previous = answers(&[(1, "11")]);
+        let current = answers(&[(2, "31")]);
+
+        assert_eq!(
+            changes(&previous, &current),
+            vec![
+                format!("Part 1: {ANSI_RED}11{ANSI_RESET} → not run"),
+                format!("Part 2: → {ANSI_GREEN}31{ANSI_RESET}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn watches_all_examples_of_a_day() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["08.txt", "08-2.txt", "08_small.txt", "18.txt", "08.md"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let mut names: Vec<_> =
+            files_in(dir.to_str().unwrap(), |name| is_example_of(name, day!(8)))
+                .iter()
+                .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+                .collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["08-2.txt", "08.txt", "08_small.txt"]);
+        assert!(files_in(dir.join("missing").to_str().unwrap(), |_| true).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
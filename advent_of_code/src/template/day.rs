@@ -1,3 +1,4 @@
+use std::env;
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -8,7 +9,50 @@ use chrono::{Datelike, FixedOffset, Utc};
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Starting with 2025, the event runs for 12 days instead of 25.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// Returns the number of days of the event in the given year.
+pub const fn event_length(year: u16) -> u8 {
+    if year >= FIRST_SHORT_YEAR {
+        12
+    } else {
+        25
+    }
+}
+
+/// Returns the year of the active event, as set through the `AOC_YEAR` environment variable.
+pub fn active_year() -> Option<u16> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
+/// Returns the last day of the active event. Defaults to the 25th if no year is set.
+pub fn last_day() -> u8 {
+    active_year().map_or(25, event_length)
+}
+
+// Not part of the public API, used by the `day!` macro to check days at compile time.
+#[doc(hidden)]
+pub const fn __last_day_of(year: Option<&str>) -> u8 {
+    let Some(year) = year else {
+        return 25;
+    };
+
+    let bytes = year.as_bytes();
+    let mut parsed: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() || i >= 4 {
+            return 25;
+        }
+        parsed = parsed * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    event_length(parsed)
+}
+
+/// A valid day number of advent (i.e. an integer in range 1 to the last day of the active event).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +69,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > last_day() {
             return None;
         }
         Some(Self(day))
@@ -45,11 +89,11 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st and the last day of the active event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(last_day()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -94,26 +138,32 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", last_day())
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the active event from the 1st to the last day.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of an event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(last_day())
+    }
+
+    /// Yields the days from the 1st up to and including `last`, e.g. `AllDays::until(event_length(year))`.
+    pub fn until(last: u8) -> Self {
+        Self { current: 1, last }
     }
 }
 
@@ -121,10 +171,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +185,16 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day is checked against the length of the event set through `AOC_YEAR` when the calling crate is compiled.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::__last_day_of(option_env!("AOC_YEAR")),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and the last day of the event"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +205,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{__last_day_of, all_days, event_length, AllDays, Day};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +238,24 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn short_event_iterator() {
+        let days: Vec<Day> = AllDays::until(event_length(2025)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
+
+    #[test]
+    fn event_lengths() {
+        assert_eq!(event_length(2015), 25);
+        assert_eq!(event_length(2024), 25);
+        assert_eq!(event_length(2025), 12);
+        assert_eq!(__last_day_of(Some("2023")), 25);
+        assert_eq!(__last_day_of(Some("2025")), 12);
+        assert_eq!(__last_day_of(Some("{Year}")), 25);
+        assert_eq!(__last_day_of(None), 25);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
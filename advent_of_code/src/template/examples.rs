@@ -0,0 +1,311 @@
+//! Examples with expected answers, declared in a front-matter header at the top of an example file:
+//!
+//! ```text
+//! ---
+//! part_1: 11
+//! part_2: 31
+//! ---
+//! 3   4
+//! 4   3
+//! ```
+//!
+//! Every other key in the header is a parameter of the example (see [`Params`]), e.g. the grid size of a smaller example.
+//! Files without a header are plain inputs.
+//! All examples of a day (`NN.txt`, `NN-<name>.txt` and `NN_<name>.txt`) are run by the test that [`crate::solution`] generates.
+use std::{fs, path::Path};
+
+use crate::template::{Day, Params, ANSI_BOLD, ANSI_RESET};
+
+static EXAMPLES_PATH: &str = "./data/examples";
+static FRONT_MATTER_DELIMITER: &str = "---";
+
+/// An example input, with the answers it is expected to produce.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub params: Params,
+    pub input: String,
+}
+
+impl Example {
+    /// Parse an example file, splitting off the front-matter header if present.
+    pub fn parse(name: &str, contents: &str) -> Result<Self, String> {
+        let mut example = Example {
+            name: name.to_string(),
+            ..Example::default()
+        };
+
+        let Some((header, input)) = split_front_matter(contents) else {
+            example.input = contents.to_string();
+            return Ok(example);
+        };
+
+        for line in header.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line.split_once(':').ok_or(format!(
+                "{name}: expected header line `{line}` to be `key: value`."
+            ))?;
+            let (key, value) = (key.trim(), value.trim().to_string());
+
+            match key {
+                "part_1" => example.part_1 = Some(value),
+                "part_2" => example.part_2 = Some(value),
+                _ => example.params.insert(key, value),
+            }
+        }
+
+        example.input = input.to_string();
+        Ok(example)
+    }
+
+    /// The expected answer of a part, if declared.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The contents of the example file, with a front-matter header if the example declares answers or parameters.
+    #[must_use]
+    pub fn contents(&self) -> String {
+        let mut header: Vec<String> = [("part_1", &self.part_1), ("part_2", &self.part_2)]
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key}: {}", value.as_deref()?)))
+            .collect();
+        header.extend(
+            self.params
+                .pairs()
+                .into_iter()
+                .map(|(key, value)| format!("{key}: {value}")),
+        );
+
+        if header.is_empty() {
+            return self.input.clone();
+        }
+
+        format!(
+            "{FRONT_MATTER_DELIMITER}\n{}\n{FRONT_MATTER_DELIMITER}\n{}",
+            header.join("\n"),
+            self.input
+        )
+    }
+}
+
+/// Returns the header and the remaining input, if the contents start with a front-matter header.
+fn split_front_matter(contents: &str) -> Option<(&str, &str)> {
+    let rest = contents.strip_prefix(FRONT_MATTER_DELIMITER)?;
+    let rest = rest
+        .strip_prefix('\n')
+        .or_else(|| rest.strip_prefix("\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == FRONT_MATTER_DELIMITER {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Strip the front-matter header of an example file, if present.
+#[must_use]
+pub fn strip_front_matter(contents: &str) -> &str {
+    split_front_matter(contents).map_or(contents, |(_, input)| input)
+}
+
+/// File names of the examples of a day in `dir`, sorted.
+fn example_names(dir: &Path, day: Day) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_example_of(name, day))
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+/// The file name of the example of a day in `dir` that is selected by `suffix`,
+/// e.g. `2` for `NN-2.txt` or `small` for `NN_small.txt`, and `NN.txt` without a suffix.
+pub(crate) fn example_file(dir: &Path, day: Day, suffix: Option<&str>) -> Result<String, String> {
+    let Some(suffix) = suffix else {
+        return Ok(format!("{day}.txt"));
+    };
+
+    let names = example_names(dir, day);
+    [format!("{day}-{suffix}.txt"), format!("{day}_{suffix}.txt")]
+        .into_iter()
+        .find(|name| names.contains(name))
+        .ok_or_else(|| {
+            let available = if names.is_empty() {
+                "none".into()
+            } else {
+                names.join(", ")
+            };
+            format!("unknown example `{suffix}` of day {day}, available: {available}.")
+        })
+}
+
+/// Read all examples of a day, sorted by file name.
+pub fn read_examples(day: Day) -> Result<Vec<Example>, String> {
+    example_names(Path::new(EXAMPLES_PATH), day)
+        .iter()
+        .map(|name| {
+            let contents = fs::read_to_string(Path::new(EXAMPLES_PATH).join(name))
+                .map_err(|e| format!("{name}: {e}"))?;
+            Example::parse(name, &contents)
+        })
+        .collect()
+}
+
+pub(crate) fn is_example_of(name: &str, day: Day) -> bool {
+    let Some(rest) = name
+        .strip_prefix(&day.to_string())
+        .and_then(|rest| rest.strip_suffix(".txt"))
+    else {
+        return false;
+    };
+
+    rest.is_empty() || rest.starts_with('-') || rest.starts_with('_')
+}
+
+/// A part of a solution, with its answer (or error) converted to a string.
+pub type ExamplePart<'a> = (
+    u8,
+    &'a dyn Fn(&str, &Params) -> Result<Option<String>, String>,
+);
+
+/// Run every part against every example of the day that declares an expected answer for it.
+/// Panics with a list of all mismatches, if any.
+pub fn run_examples(day: Day, parts: &[ExamplePart]) {
+    let examples = read_examples(day).unwrap_or_else(|e| panic!("{e}"));
+    let mut failures: Vec<String> = vec![];
+
+    for example in &examples {
+        for (part, func) in parts {
+            let Some(expected) = example.expected(*part) else {
+                continue;
+            };
+
+            let actual = func(&example.input, &example.params);
+
+            if actual
+                .as_ref()
+                .is_ok_and(|a| a.as_deref() == Some(expected))
+            {
+                println!(
+                    "{}, part {part}: {ANSI_BOLD}{expected}{ANSI_RESET} ✓",
+                    example.name
+                );
+            } else {
+                let actual = match actual {
+                    Ok(answer) => answer.unwrap_or_else(|| "✖".into()),
+                    Err(e) => format!("✖ ({e})"),
+                };
+                failures.push(format!(
+                    "{}, part {part}: expected {expected}, got {actual}",
+                    example.name
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{example_file, is_example_of, strip_front_matter, Example};
+    use crate::day;
+
+    #[test]
+    fn parses_front_matter() {
+        let example = Example::parse(
+            "14-small.txt",
+            "---\npart_1: 12\nwidth: 11\nheight : 7\n---\np=0,4 v=3,-3\n",
+        )
+        .unwrap();
+
+        assert_eq!(example.expected(1), Some("12"));
+        assert_eq!(example.expected(2), None);
+        assert_eq!(example.params.get("width"), Some(11));
+        assert_eq!(example.params.get("height"), Some(7));
+        assert_eq!(example.input, "p=0,4 v=3,-3\n");
+    }
+
+    #[test]
+    fn handles_plain_examples() {
+        let example = Example::parse("01.txt", "3   4\n---\n").unwrap();
+        assert_eq!(example.expected(1), None);
+        assert_eq!(example.input, "3   4\n---\n");
+        assert_eq!(strip_front_matter("3   4\n"), "3   4\n");
+    }
+
+    #[test]
+    fn writes_front_matter() {
+        let mut example = Example {
+            name: "14.txt".into(),
+            part_1: Some("12".into()),
+            input: "p=0,4 v=3,-3\n".into(),
+            ..Example::default()
+        };
+        example.params.insert("width", 11);
+        assert_eq!(
+            example.contents(),
+            "---\npart_1: 12\nwidth: 11\n---\np=0,4 v=3,-3\n"
+        );
+        assert_eq!(Example::parse("14.txt", &example.contents()), Ok(example));
+
+        let plain = Example::parse("01.txt", "3   4\n").unwrap();
+        assert_eq!(plain.contents(), "3   4\n");
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        assert!(Example::parse("01.txt", "---\npart_1 11\n---\n").is_err());
+    }
+
+    #[test]
+    fn matches_example_files() {
+        assert!(is_example_of("08.txt", day!(8)));
+        assert!(is_example_of("08-2.txt", day!(8)));
+        assert!(is_example_of("08_2.txt", day!(8)));
+        assert!(!is_example_of("18.txt", day!(8)));
+        assert!(!is_example_of("08.md", day!(8)));
+    }
+
+    #[test]
+    fn selects_examples_by_suffix() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["08.txt", "08-2.txt", "08_small.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        assert_eq!(example_file(&dir, day!(8), None).unwrap(), "08.txt");
+        assert_eq!(example_file(&dir, day!(8), Some("2")).unwrap(), "08-2.txt");
+        assert_eq!(
+            example_file(&dir, day!(8), Some("small")).unwrap(),
+            "08_small.txt"
+        );
+        assert_eq!(
+            example_file(&dir, day!(8), Some("large")).unwrap_err(),
+            "unknown example `large` of day 08, available: 08-2.txt, 08.txt, 08_small.txt."
+        );
+        assert!(example_file(&dir, day!(9), Some("2"))
+            .unwrap_err()
+            .ends_with("available: none."));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::template::{
+    examples::{example_file, Example},
+    Day, Params,
+};
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// Any file, e.g. someone else's puzzle input.
+    File(PathBuf),
+    /// Standard input, selected with `--input -`.
+    Stdin,
+    /// An example in `data/examples`, optionally with a suffix, i.e. `NN.txt`, `NN-<suffix>.txt` or `NN_<suffix>.txt`.
+    Example(Option<String>),
+}
+
+impl InputSource {
+    /// Read the input source from the flags passed to a solution binary, `--input <path | ->` or `--example [suffix]`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let position = |flag: &str| args.iter().position(|x| x == flag);
+
+        match (position("--input"), position("--example")) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` are mutually exclusive.".into()),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Ok(Self::Stdin),
+                Some(path) => Ok(Self::File(path.into())),
+                None => Err("expected `--input` to be followed by a path or `-`.".into()),
+            },
+            (None, Some(i)) => Ok(Self::Example(
+                args.get(i + 1).filter(|x| !x.starts_with('-')).cloned(),
+            )),
+            (None, None) => Ok(Self::Puzzle),
+        }
+    }
+
+    /// The flags that pass this input source on to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(suffix)) => vec!["--example".into(), suffix.clone()],
+        }
+    }
+
+    /// Read the input of a day.
+    /// Examples also return the parameters declared in their header (see [`crate::template::examples`]).
+    pub fn read(&self, day: Day) -> Result<(String, Params), InputError> {
+        match self {
+            Self::Puzzle => Ok((read_file("inputs", day)?, Params::default())),
+            Self::File(path) => Ok((read_to_string(path.clone())?, Params::default())),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok((input, Params::default()))
+            }
+            Self::Example(suffix) => {
+                let name = example_file(&data_path("examples"), day, suffix.as_deref())
+                    .map_err(InputError::Example)?;
+                let contents = read_to_string(data_path("examples").join(&name))?;
+                let example = Example::parse(&name, &contents).map_err(InputError::Example)?;
+                Ok((example.input, example.params))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    File(PathBuf, io::Error),
+    Stdin(io::Error),
+    Example(String),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::File(path, e) => write!(f, "could not read \"{}\": {e}", path.display()),
+            InputError::Stdin(e) => write!(f, "could not read stdin: {e}"),
+            InputError::Example(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn data_path(folder: &str) -> PathBuf {
+    env::current_dir().unwrap().join("data").join(folder)
+}
+
+fn read_to_string(path: PathBuf) -> Result<String, InputError> {
+    fs::read_to_string(&path).map_err(|e| InputError::File(path, e))
+}
+
+/// Helper function that reads a text file to a string.
+/// The front-matter header of example files (see [`crate::template::examples`]) is stripped.
+pub fn read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    let contents = read_to_string(data_path(folder).join(format!("{day}.txt")))?;
+    Ok(strip_example_header(folder, contents))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    let contents = read_to_string(data_path(folder).join(format!("{day}-{part}.txt")))?;
+    Ok(strip_example_header(folder, contents))
+}
+
+fn strip_example_header(folder: &str, contents: String) -> String {
+    if folder == "examples" {
+        crate::template::examples::strip_front_matter(&contents).to_string()
+    } else {
+        contents
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, InputSource};
+    use crate::day;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_flags() {
+        assert_eq!(
+            InputSource::from_args(&args(&["01"])),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "-"])),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "other.txt", "--time"])),
+            Ok(InputSource::File("other.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "--time"])),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "2"])),
+            Ok(InputSource::Example(Some("2".into())))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "small", "--time"])),
+            Ok(InputSource::Example(Some("small".into())))
+        );
+        assert!(InputSource::from_args(&args(&["01", "--input"])).is_err());
+        assert!(InputSource::from_args(&args(&["--input", "a", "--example"])).is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Stdin,
+            InputSource::File("other.txt".into()),
+            InputSource::Example(None),
+            InputSource::Example(Some("2".into())),
+            InputSource::Example(Some("small".into())),
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+        }
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let error = InputSource::File("does/not/exist.txt".into())
+            .read(day!(1))
+            .unwrap_err();
+        assert!(matches!(error, InputError::File(..)));
+        assert!(error
+            .to_string()
+            .starts_with("could not read \"does/not/exist.txt\""));
+    }
+}
@@ -1,67 +1,224 @@
-use std::{env, fs};
-
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod cli;
 pub mod commands;
+pub mod examples;
+pub mod output;
 pub mod runner;
+pub mod years;
 
 pub use day::*;
+pub use input::{read_file, read_file_part, InputError, InputSource};
+pub use output::Ansi;
+pub use params::Params;
 
+mod answers;
 mod day;
+mod input;
+mod params;
+mod readme;
 mod run_multi;
 mod timings;
 
-pub const ANSI_ITALIC: &str = "\x1b[3m";
-pub const ANSI_BOLD: &str = "\x1b[1m";
-pub const ANSI_RESET: &str = "\x1b[0m";
-
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
+pub const ANSI_ITALIC: Ansi = Ansi::new("\x1b[3m");
+pub const ANSI_BOLD: Ansi = Ansi::new("\x1b[1m");
+pub const ANSI_RED: Ansi = Ansi::new("\x1b[31m");
+pub const ANSI_GREEN: Ansi = Ansi::new("\x1b[32m");
+pub const ANSI_RESET: Ansi = Ansi::new("\x1b[0m");
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also creates the constant `SOLUTION`, which registers the day with the in-process runner (see the `aoc_registry` crate).
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse = <fn>` runs that function once on the input and hands a reference to its result to both parts,
+/// e.g. `solution!(1, parse = parse)` with `fn parse(input: &str) -> Grid` and `fn part_one(grid: &Grid) -> Option<u32>`.
+/// Parse time is then reported separately from the time spent in each part.
+///
+/// Parts may also take puzzle parameters as a second argument, e.g. `fn part_one(input: &str, params: &Params) -> Option<u32>`
+/// (see [`Params`]), and may return `Result<Option<T>, E>` to report errors such as malformed input (see [`runner::PartOutput`]).
+///
+/// Passing `variants = [<name>(<part one>, <part two>), ...]` declares alternate implementations of the day,
+/// e.g. `solution!(7, variants = [original(part_one, part_two_original)])`.
+/// They are run with `cargo solve 07 --variant original`, or compared with the default one with `cargo solve 07 --compare`
+/// (see [`runner::Variant`]).
+///
+/// In test builds, a test is generated that runs every example of the day declaring expected answers (see [`examples`]),
+/// once for each variant.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1];);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2];);
+    };
+    ($day:expr, variants = [$( $name:ident($one:expr, $two:expr) ),* $(,)?]) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]; $( $name([$one, 1] [$two, 2]) )*);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2];);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1];);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2];);
+    };
+    ($day:expr, parse = $parse:expr, variants = [$( $name:ident($one:expr, $two:expr) ),* $(,)?]) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]; $( $name([$one, 1] [$two, 2]) )*);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*; $( $name:ident($( [$vfunc:expr, $vpart:expr] )*) )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The implementations of this day, the first one is made up of `part_one` and `part_two`.
+        #[allow(dead_code)]
+        pub const VARIANTS: &[$crate::template::runner::Variant] = &[
+            $crate::template::runner::Variant {
+                name: $crate::template::runner::DEFAULT_VARIANT,
+                run: $crate::solution!(@run parse = $parse, $( [$func, $part] )*),
+            },
+            $(
+                $crate::template::runner::Variant {
+                    name: stringify!($name),
+                    run: $crate::solution!(@run parse = $parse, $( [$vfunc, $vpart] )*),
+                },
+            )*
+        ];
+
+        $crate::solution!(@main);
+
+        #[cfg(test)]
+        mod solution_examples {
+            use super::*;
+            use $crate::template::runner::PartFn;
+
+            /// Runs every example in `data/examples` that declares expected answers.
+            #[test]
+            fn examples() {
+                $crate::template::examples::run_examples(DAY, &[$(
+                    ($part, &|input: &str, params: &$crate::template::Params| {
+                        PartFn::call(&$func, &$parse(input), params)
+                            .map(|answer| answer.map(|a| a.to_string()))
+                    }),
+                )*]);
+            }
+
+            mod variants {
+                use super::*;
+
+                $(
+                    /// Runs every example in `data/examples` that declares expected answers against this variant.
+                    #[test]
+                    fn $name() {
+                        $crate::template::examples::run_examples(DAY, &[$(
+                            ($vpart, &|input: &str, params: &$crate::template::Params| {
+                                PartFn::call(&$vfunc, &$parse(input), params)
+                                    .map(|answer| answer.map(|a| a.to_string()))
+                            }),
+                        )*]);
+                    }
+                )*
+            }
+        }
+    };
+
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*; $( $name:ident($( [$vfunc:expr, $vpart:expr] )*) )*) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The implementations of this day, the first one is made up of `part_one` and `part_two`.
+        #[allow(dead_code)]
+        pub const VARIANTS: &[$crate::template::runner::Variant] = &[
+            $crate::template::runner::Variant {
+                name: $crate::template::runner::DEFAULT_VARIANT,
+                run: $crate::solution!(@run $( [$func, $part] )*),
+            },
+            $(
+                $crate::template::runner::Variant {
+                    name: stringify!($name),
+                    run: $crate::solution!(@run $( [$vfunc, $vpart] )*),
+                },
+            )*
+        ];
+
+        $crate::solution!(@main);
+
+        #[cfg(test)]
+        mod solution_examples {
+            use super::*;
+            use $crate::template::runner::PartFn;
+
+            /// Runs every example in `data/examples` that declares expected answers.
+            #[test]
+            fn examples() {
+                $crate::template::examples::run_examples(DAY, &[$(
+                    ($part, &|input: &str, params: &$crate::template::Params| {
+                        PartFn::call(&$func, input, params)
+                            .map(|answer| answer.map(|a| a.to_string()))
+                    }),
+                )*]);
+            }
+
+            mod variants {
+                use super::*;
+
+                $(
+                    /// Runs every example in `data/examples` that declares expected answers against this variant.
+                    #[test]
+                    fn $name() {
+                        $crate::template::examples::run_examples(DAY, &[$(
+                            ($vpart, &|input: &str, params: &$crate::template::Params| {
+                                PartFn::call(&$vfunc, input, params)
+                                    .map(|answer| answer.map(|a| a.to_string()))
+                            }),
+                        )*]);
+                    }
+                )*
+            }
+        }
+    };
+
+    (@run parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        |input, options| {
+            use $crate::template::runner::*;
+            let (parsed, parse_result) = run_parse_with($parse, input, DAY, options);
+            let mut results = vec![parse_result];
+            if let Some(parsed) = parsed {
+                $( results.push(run_part_with($func, &parsed, DAY, $part, options)); )*
+            }
+            results
+        }
+    };
+
+    (@run $( [$func:expr, $part:expr] )*) => {
+        |input, options| {
+            use $crate::template::runner::*;
+            vec![$( run_part_with($func, input, DAY, $part, options), )*]
+        }
+    };
+
+    (@main) => {
+        /// Entry point for running this day from the year binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            day: DAY,
+            run: VARIANTS[0].run,
+        };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let mut options = RunOptions::from_args();
+            let input = read_input(DAY, &mut options);
+            run_variants(VARIANTS, &input, DAY, &options);
         }
     };
 }
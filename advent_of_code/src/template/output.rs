@@ -0,0 +1,358 @@
+//! How results are printed: as decorated text for a terminal, or as structured records for other tools.
+//! ANSI codes are only written when stdout is a terminal, unless forced with [`FORCE_COLOR_ENV`] or disabled with `NO_COLOR`.
+use std::cell::RefCell;
+use std::env;
+use std::fmt::{self, Display, Write};
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use tinyjson::JsonValue;
+
+use crate::template::active_year;
+use crate::template::answers::Verdict;
+use crate::template::runner::PartResult;
+use crate::template::timings::Failure;
+
+/// Like `print!`, but captured if the current thread runs in [`capture`].
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write_out(format_args!($($arg)*))
+    };
+}
+
+/// Like `println!`, but captured if the current thread runs in [`capture`].
+macro_rules! outln {
+    () => {
+        $crate::template::output::write_out(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::write_out(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {out, outln};
+
+/// If set, ANSI codes are written even if stdout is not a terminal, e.g. for child processes with captured output.
+pub const FORCE_COLOR_ENV: &str = "CLICOLOR_FORCE";
+
+/// Columns of the `tsv` format, also the fields of the `json` format.
+pub const RECORD_FIELDS: [&str; 7] = [
+    "year",
+    "day",
+    "part",
+    "answer",
+    "duration_nanos",
+    "samples",
+    "status",
+];
+
+/// The format of the results printed by `solve`, `all` and `time`, selected with `--format`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text, with progress output and ANSI codes on a terminal.
+    #[default]
+    Text,
+    /// One JSON object per part and line.
+    Json,
+    /// One tab-separated line per part, after a header line (see [`RECORD_FIELDS`]).
+    Tsv,
+}
+
+impl OutputFormat {
+    #[must_use]
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+
+    /// The flags that pass this format on to a solution binary.
+    #[must_use]
+    pub fn to_args(self) -> Vec<String> {
+        if self.is_text() {
+            vec![]
+        } else {
+            vec!["--format".into(), self.to_string()]
+        }
+    }
+
+    /// Print the header line of the format, if it has one.
+    pub fn print_header(self) {
+        if self == OutputFormat::Tsv {
+            println!("{}", RECORD_FIELDS.join("\t"));
+        }
+    }
+
+    /// Print the record of a part in this format. Text output is printed while the part runs instead.
+    pub fn print_record(self, result: &PartResult) {
+        let year = active_year();
+
+        match self {
+            OutputFormat::Text => {}
+            OutputFormat::Json => match json_record(year, result) {
+                Ok(record) => outln!("{record}"),
+                Err(e) => eprintln!("failed to serialize result record: {e}"),
+            },
+            OutputFormat::Tsv => outln!("{}", tsv_record(year, result)),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(format!(
+                "unknown format `{s}`, expected one of: text, json, tsv."
+            )),
+        }
+    }
+}
+
+/// Whether stdout is a terminal, i.e. whether progress output that is overwritten with `\r` is useful.
+#[must_use]
+pub fn is_terminal() -> bool {
+    static IS_TERMINAL: OnceLock<bool> = OnceLock::new();
+    *IS_TERMINAL.get_or_init(|| io::stdout().is_terminal())
+}
+
+/// Whether ANSI codes are written, see the module docs.
+#[must_use]
+pub fn colors_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        let is_set = |name: &str| env::var(name).is_ok_and(|v| !v.is_empty() && v != "0");
+
+        if is_set("NO_COLOR") {
+            false
+        } else {
+            is_set(FORCE_COLOR_ENV) || is_terminal()
+        }
+    })
+}
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f` and return the output it writes through [`out!`] and [`outln!`] instead of printing it.
+/// This lets `run_multi` run days on worker threads and print their output in day order.
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURED.with_borrow_mut(|captured| captured.replace(String::new()));
+    let result = f();
+    let output = CAPTURED.with_borrow_mut(|captured| std::mem::replace(captured, previous));
+
+    (result, output.unwrap_or_default())
+}
+
+/// Whether the current thread runs in [`capture`], progress output is not useful then.
+#[must_use]
+pub(crate) fn is_capturing() -> bool {
+    CAPTURED.with_borrow(Option::is_some)
+}
+
+#[doc(hidden)]
+pub(crate) fn write_out(args: fmt::Arguments) {
+    CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(buffer) => {
+            let _ = buffer.write_fmt(args);
+        }
+        None => print!("{args}"),
+    });
+}
+
+/// An ANSI escape code that is left out if colors are disabled (see [`colors_enabled`]).
+#[derive(Clone, Copy, Debug)]
+pub struct Ansi(&'static str);
+
+impl Ansi {
+    #[must_use]
+    pub const fn new(code: &'static str) -> Self {
+        Self(code)
+    }
+}
+
+impl Display for Ansi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if colors_enabled() {
+            f.write_str(self.0)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of a part in a single word: `ok`, `unsolved`, `pass`, `fail`, `timeout` or `panicked`.
+/// The parse step is `ok` if it did not fail, even though it has no answer.
+fn status(result: &PartResult) -> &'static str {
+    match (&result.failure, &result.verdict) {
+        (Some(Failure::Timeout(_)), _) => "timeout",
+        (Some(Failure::Panicked(_)), _) => "panicked",
+        (None, Some(Verdict::Pass)) => "pass",
+        (None, Some(Verdict::Fail { .. })) => "fail",
+        _ if result.part == 0 || result.answer.is_some() => "ok",
+        _ => "unsolved",
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn duration_nanos(result: &PartResult) -> u64 {
+    result.timing.mean_nanos as u64
+}
+
+/// The fields are written in the order of [`RECORD_FIELDS`], which a `JsonValue::Object` would not keep.
+fn json_record(year: Option<u16>, result: &PartResult) -> Result<String, String> {
+    #[allow(clippy::cast_precision_loss)]
+    let number = |n: u64| JsonValue::Number(n as f64);
+
+    let values = [
+        year.map_or(JsonValue::Null, |year| number(year.into())),
+        number(result.day.into_inner().into()),
+        number(result.part.into()),
+        result
+            .answer
+            .clone()
+            .map_or(JsonValue::Null, JsonValue::String),
+        number(duration_nanos(result)),
+        number(result.timing.samples),
+        JsonValue::String(status(result).into()),
+    ];
+
+    let fields = RECORD_FIELDS
+        .iter()
+        .zip(values)
+        .map(|(field, value)| {
+            let value = value.stringify().map_err(|e| e.to_string())?;
+            Ok(format!("\"{field}\":{value}"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(format!("{{{}}}", fields.join(",")))
+}
+
+/// Tabs and line breaks in answers, e.g. of ASCII art, are escaped to keep one line per record.
+fn tsv_record(year: Option<u16>, result: &PartResult) -> String {
+    let answer = result
+        .answer
+        .as_deref()
+        .unwrap_or_default()
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n");
+
+    [
+        year.map(|year| year.to_string()).unwrap_or_default(),
+        result.day.into_inner().to_string(),
+        result.part.to_string(),
+        answer,
+        duration_nanos(result).to_string(),
+        result.timing.samples.to_string(),
+        status(result).to_string(),
+    ]
+    .join("\t")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{capture, is_capturing, json_record, tsv_record, OutputFormat};
+    use crate::{
+        day,
+        template::{
+            answers::Verdict,
+            runner::PartResult,
+            timings::{Failure, PartTiming},
+        },
+    };
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: day!(7),
+            part,
+            answer: answer.map(ToString::to_string),
+            timing: PartTiming::from_samples(&[Duration::from_nanos(1500)]),
+            verdict: None,
+            failure: None,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("tsv".parse(), Ok(OutputFormat::Tsv));
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert!("csv".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Text.to_args(), Vec::<String>::new());
+        assert_eq!(OutputFormat::Tsv.to_args(), vec!["--format", "tsv"]);
+    }
+
+    #[test]
+    fn formats_json_records() {
+        assert_eq!(
+            json_record(Some(2024), &result(1, Some("4\"2"))).unwrap(),
+            r#"{"year":2024,"day":7,"part":1,"answer":"4\"2","duration_nanos":1500,"samples":1,"status":"ok"}"#
+        );
+
+        let json: JsonValue = json_record(None, &result(2, None))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+        assert!(map["year"].is_null());
+        assert!(map["answer"].is_null());
+        assert_eq!(map["status"], JsonValue::String("unsolved".into()));
+    }
+
+    #[test]
+    fn formats_tsv_records() {
+        assert_eq!(
+            tsv_record(Some(2024), &result(0, None)),
+            "2024\t7\t0\t\t1500\t1\tok"
+        );
+
+        let mut verified = result(2, Some("#.\n.#"));
+        verified.verdict = Some(Verdict::Fail {
+            expected: "#".into(),
+        });
+        assert_eq!(
+            tsv_record(None, &verified),
+            "\t7\t2\t#.\\n.#\t1500\t1\tfail"
+        );
+
+        let mut failed = result(1, None);
+        failed.failure = Some(Failure::Timeout(Duration::from_secs(1)));
+        assert!(tsv_record(Some(2024), &failed).ends_with("\ttimeout"));
+    }
+
+    #[test]
+    fn captures_output_of_the_current_thread() {
+        let (result, output) = capture(|| {
+            outln!("Part {}: {}", 1, 42);
+            out!("Part 2");
+            is_capturing()
+        });
+
+        assert!(result);
+        assert_eq!(output, "Part 1: 42\nPart 2");
+        assert!(!is_capturing());
+    }
+}
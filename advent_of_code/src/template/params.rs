@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Named puzzle parameters that differ between the real input and the examples, e.g. the size of a grid.
+///
+/// Solutions declare the value for the real input as a default and read overrides from the context,
+/// e.g. `params.get_or("width", 101)`.
+///
+/// Overrides come from the `--param key=value` flag of a solution binary, or from the header of an example file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    /// Returns the parameter parsed as `T`, if set.
+    ///
+    /// # Panics
+    /// If the parameter is set, but can not be parsed as `T`.
+    #[must_use]
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        let value = self.0.get(key)?;
+        match value.parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => panic!("invalid value `{value}` for parameter `{key}`"),
+        }
+    }
+
+    /// Returns the parameter parsed as `T`, or `default` if not set.
+    #[must_use]
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> T {
+        self.get(key).unwrap_or(default)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Display) {
+        self.0.insert(key.into(), value.to_string());
+    }
+
+    /// Add the parameters of `defaults` that are not set yet.
+    pub fn set_defaults(&mut self, defaults: Params) {
+        for (key, value) in defaults.0 {
+            self.0.entry(key).or_insert(value);
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The parameters as `(key, value)` pairs, sorted by key.
+    #[must_use]
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        let mut pairs: Vec<_> = self
+            .0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// The flags that pass these parameters on to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        self.pairs()
+            .into_iter()
+            .flat_map(|(key, value)| ["--param".into(), format!("{key}={value}")])
+            .collect()
+    }
+
+    /// Parse a `key=value` pair, as passed to `--param`.
+    pub fn parse_pair(s: &str) -> Result<(String, String), String> {
+        s.split_once('=')
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or(format!("expected parameter `{s}` to be `key=value`"))
+    }
+}
+
+impl From<HashMap<String, String>> for Params {
+    fn from(value: HashMap<String, String>) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Params {
+    fn from(value: [(&str, &str); N]) -> Self {
+        Self(
+            value
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<T: IntoIterator<Item = (String, String)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Params;
+
+    #[test]
+    fn reads_typed_values() {
+        let params = Params::from([("size", "7"), ("blocks", "12")]);
+        assert_eq!(params.get::<usize>("size"), Some(7));
+        assert_eq!(params.get_or("blocks", 1024_usize), 12);
+        assert_eq!(params.get_or("cutoff", 100_usize), 100);
+    }
+
+    #[test]
+    fn overrides_defaults_of_the_real_input() {
+        let params = Params::from([("width", "11")]);
+        assert_eq!(params.get_or("width", 101), 11);
+        assert_eq!(params.get_or("height", 103), 103);
+    }
+
+    #[test]
+    fn keeps_values_over_defaults() {
+        let mut params = Params::from([("size", "9")]);
+        params.set_defaults(Params::from([("size", "7"), ("blocks", "12")]));
+        assert_eq!(params, Params::from([("size", "9"), ("blocks", "12")]));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid value `seven` for parameter `size`")]
+    fn panics_for_invalid_values() {
+        let params = Params::from([("size", "seven")]);
+        let _: Option<usize> = params.get("size");
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let params = Params::from([("width", "11"), ("height", "7")]);
+        assert_eq!(
+            params.to_args(),
+            vec!["--param", "height=7", "--param", "width=11"]
+        );
+
+        let parsed: Params = ["height=7", "width = 11"]
+            .iter()
+            .map(|s| Params::parse_pair(s).unwrap())
+            .collect();
+        assert_eq!(parsed, params);
+        assert!(Params::parse_pair("width").is_err());
+    }
+}
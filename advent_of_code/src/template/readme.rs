@@ -0,0 +1,253 @@
+//! Module that updates the results table in the year's `README.md` and the years table in the workspace `README.md`.
+//! Both tables are delimited by a pair of marker comments, everything in between is regenerated.
+use std::{fmt::Display, fs, io, ops::Range};
+
+use crate::template::{active_year, event_length, timings::Timings, AllDays, Day};
+
+static RESULTS_MARKER: &str = "<!--- results table --->";
+static YEARS_MARKER: &str = "<!--- years table --->";
+
+static README_PATH: &str = "./README.md";
+static ROOT_README_PATH: &str = "../README.md";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Regenerate the results table of the current year and its row in the workspace years table.
+/// `scaffolded` are the days that have a solution file.
+pub fn update(timings: &Timings, scaffolded: &[Day]) -> Result<(), Error> {
+    let year = active_year().ok_or_else(|| Error::Parser("AOC_YEAR is not set.".into()))?;
+
+    let mut readme = fs::read_to_string(README_PATH)?;
+    update_results_table(&mut readme, year, timings, scaffolded)?;
+    fs::write(README_PATH, &readme)?;
+
+    let mut root_readme = fs::read_to_string(ROOT_README_PATH)?;
+    update_years_table(&mut root_readme, year, count_stars(timings))?;
+    fs::write(ROOT_README_PATH, &root_readme)?;
+
+    Ok(())
+}
+
+/// Add a row without any stars for a new year to the years table of the workspace README.
+pub fn add_year(root_readme: &str, year: u16) -> Result<String, Error> {
+    let mut root_readme = root_readme.to_string();
+    update_years_table(&mut root_readme, year, 0)?;
+    Ok(root_readme)
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<Range<usize>, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() != 2 {
+        return Err(Error::Parser(format!(
+            "expected README to contain the marker `{marker}` exactly twice, found {}.",
+            matches.len()
+        )));
+    }
+
+    Ok(matches[0].0..matches[1].0 + marker.len())
+}
+
+fn count_stars(timings: &Timings) -> usize {
+    timings
+        .data
+        .iter()
+        .map(|t| usize::from(t.part_1.is_some()) + usize::from(t.part_2.is_some()))
+        .sum()
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = std::time::Duration::from_nanos(nanos as u64);
+    format!("`{duration:.1?}`")
+}
+
+fn construct_results_table(year: u16, timings: &Timings, scaffolded: &[Day]) -> String {
+    let mut lines: Vec<String> = vec![
+        RESULTS_MARKER.into(),
+        "| Day | Code | Part 1 | Part 2 | Time |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    let star =
+        |part: Option<f64>| part.map_or("_".into(), |nanos| format!("⭐ {}", format_nanos(nanos)));
+
+    for day in AllDays::until(event_length(year)) {
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        let code = if scaffolded.contains(&day) {
+            format!("[code](src/bin/{day}.rs)")
+        } else {
+            "_".into()
+        };
+
+        let part_1 = star(timing.and_then(|t| t.part_1.as_ref()).map(|p| p.mean_nanos));
+        let part_2 = star(timing.and_then(|t| t.part_2.as_ref()).map(|p| p.mean_nanos));
+        let total = timing.map_or("_".into(), |t| format_nanos(t.total_nanos));
+
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {code} | {part_1} | {part_2} | {total} |",
+            day.into_inner(),
+            day.into_inner(),
+        ));
+    }
+
+    let part_1_stars = timings.data.iter().filter(|t| t.part_1.is_some()).count();
+    let part_2_stars = timings.data.iter().filter(|t| t.part_2.is_some()).count();
+
+    lines.push(format!(
+        "| **Total** | | **{part_1_stars} ⭐** | **{part_2_stars} ⭐** | **{}** |",
+        format_nanos(timings.total_millis() * 1_000_000_f64),
+    ));
+
+    lines.push(RESULTS_MARKER.into());
+    lines.join("\n")
+}
+
+fn update_results_table(
+    readme: &mut String,
+    year: u16,
+    timings: &Timings,
+    scaffolded: &[Day],
+) -> Result<(), Error> {
+    let range = locate_table(readme, RESULTS_MARKER)?;
+    let table = construct_results_table(year, timings, scaffolded);
+    readme.replace_range(range, &table);
+    Ok(())
+}
+
+/// Replace the row of `year` in the years table, adding it if it is not present yet.
+fn update_years_table(readme: &mut String, year: u16, stars: usize) -> Result<(), Error> {
+    let range = locate_table(readme, YEARS_MARKER)?;
+
+    let mut rows: Vec<(u16, String)> = readme[range.clone()]
+        .lines()
+        .filter_map(|line| {
+            let row_year = line.strip_prefix("| [")?.split(']').next()?.parse().ok()?;
+            Some((row_year, line.to_string()))
+        })
+        .filter(|(row_year, _)| *row_year != year)
+        .collect();
+
+    let total = usize::from(event_length(year)) * 2;
+    rows.push((year, format!("| [{year}](aoc{year}) | {stars}/{total} |")));
+    rows.sort_unstable_by_key(|(row_year, _)| std::cmp::Reverse(*row_year));
+
+    let mut lines: Vec<String> = vec![
+        YEARS_MARKER.into(),
+        "| Year | Completed |".into(),
+        "| :---: | :---: |".into(),
+    ];
+    lines.extend(rows.into_iter().map(|(_, line)| line));
+    lines.push(YEARS_MARKER.into());
+
+    readme.replace_range(range, &lines.join("\n"));
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_results_table, update_years_table, Error};
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        let part =
+            |nanos: f64| PartTiming::from_samples(&[std::time::Duration::from_nanos(nanos as u64)]);
+
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some(part(1e+6)),
+                    part_2: Some(part(2e+6)),
+                    total_nanos: 3e+6,
+                    failures: vec![],
+                    memory: vec![],
+                },
+                Timing {
+                    day: day!(2),
+                    parse: None,
+                    part_1: Some(part(4e+6)),
+                    part_2: None,
+                    total_nanos: 4e+6,
+                    failures: vec![],
+                    memory: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn updates_results_table() {
+        let mut readme = String::from(
+            "# 2024\n\n<!--- results table --->\n| old |\n<!--- results table --->\n\n---\n",
+        );
+
+        update_results_table(&mut readme, 2024, &get_mock_timings(), &[day!(1), day!(2)]).unwrap();
+
+        assert!(readme.starts_with("# 2024\n\n<!--- results table --->\n| Day |"));
+        assert!(readme.ends_with("<!--- results table --->\n\n---\n"));
+        assert!(!readme.contains("| old |"));
+        assert!(readme.contains(
+            "| [Day 1](https://adventofcode.com/2024/day/1) | [code](src/bin/01.rs) | ⭐ `1.0ms` | ⭐ `2.0ms` | `3.0ms` |"
+        ));
+        assert!(readme.contains(
+            "| [Day 2](https://adventofcode.com/2024/day/2) | [code](src/bin/02.rs) | ⭐ `4.0ms` | _ | `4.0ms` |"
+        ));
+        assert!(readme.contains("| [Day 3](https://adventofcode.com/2024/day/3) | _ | _ | _ | _ |"));
+        assert!(readme.contains("| **Total** | | **2 ⭐** | **1 ⭐** | **`7.0ms`** |"));
+    }
+
+    #[test]
+    fn updates_years_table() {
+        let mut readme = String::from(concat!(
+            "## Years\n\n<!--- years table --->\n",
+            "| Year | Completed |\n| :---: | :---: |\n",
+            "| [2024](aoc2024) | 50/50 |\n| [2015](aoc2015) | 24/50 |\n",
+            "<!--- years table --->\n"
+        ));
+
+        update_years_table(&mut readme, 2015, 26).unwrap();
+        update_years_table(&mut readme, 2023, 50).unwrap();
+
+        assert_eq!(
+            readme,
+            concat!(
+                "## Years\n\n<!--- years table --->\n",
+                "| Year | Completed |\n| :---: | :---: |\n",
+                "| [2024](aoc2024) | 50/50 |\n| [2023](aoc2023) | 50/50 |\n| [2015](aoc2015) | 26/50 |\n",
+                "<!--- years table --->\n"
+            )
+        );
+    }
+
+    #[test]
+    fn errors_without_markers() {
+        let mut readme = String::from("# 2024\n");
+        let res = update_results_table(&mut readme, 2024, &get_mock_timings(), &[]);
+        assert!(matches!(res, Err(Error::Parser(_))));
+    }
+}
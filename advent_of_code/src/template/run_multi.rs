@@ -1,55 +1,327 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::Path,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    answers::Verdict,
+    output,
+    runner::{write_record, PartResult, RunOptions, Solution},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+/// Result of checking one part of a day against its accepted answer.
+#[derive(Clone, Debug)]
+pub struct Verification {
+    pub day: Day,
+    pub part: u8,
+    pub verdict: Verdict,
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// Everything collected while running a set of days.
+#[derive(Clone, Debug, Default)]
+pub struct MultiRun {
+    pub timings: Option<Timings>,
+    pub verifications: Vec<Verification>,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Run a set of days in day order.
+/// Days that are linked into the year binary run in-process, all others are run as child processes.
+///
+/// With `options.jobs` above one, days run concurrently on worker threads instead (see [`run_parallel`]).
+/// Timed runs always run serially, so that days do not skew each other's timings.
+/// Memory runs also run serially, as each profiled child process is built with the `dhat-heap` feature.
+///
+/// With a structured `options.format`, only the record of each part is printed (see [`super::output::OutputFormat`]).
+pub fn run_multi(
+    solutions: &[Solution],
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    options: &RunOptions,
+) -> MultiRun {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut verifications: Vec<Verification> = vec![];
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut collect = |day: Day, results: Vec<PartResult>| {
+        // pass results on to a process running several years, if any.
+        results.iter().for_each(write_record);
+
+        timings.push(timing_from_results(day, &results));
+        verifications.extend(results.into_iter().filter_map(|result| {
+            Some(Verification {
+                day,
+                part: result.part,
+                verdict: result.verdict?,
+            })
+        }));
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if options.jobs > 1 && !options.timed && !options.memory {
+        run_parallel(solutions, &days, is_release, options, &mut collect);
+    } else {
+        for (i, day) in days.iter().copied().enumerate() {
+            if options.format.is_text() {
+                print_day_header(day, i > 0);
+            }
+            if let Some(results) = run_day(solutions, day, is_release, options) {
+                collect(day, results);
             }
-        });
+        }
+    }
+
+    let timings = (options.timed || options.memory).then_some(Timings { data: timings });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if let (Some(timings), true) = (&timings, options.timed && options.format.is_text()) {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+    }
+
+    MultiRun {
+        timings,
+        verifications,
+    }
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run a single day, printing its output as it goes.
+/// Returns `None` if the day has not been scaffolded or there is no input for it.
+fn run_day(
+    solutions: &[Solution],
+    day: Day,
+    is_release: bool,
+    options: &RunOptions,
+) -> Option<Vec<PartResult>> {
+    // skip days that have not been scaffolded yet.
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        if options.format.is_text() {
+            println!("Not solved.");
+        }
+        return None;
+    }
+
+    if let Some(solution) = linked_solution(solutions, day, options) {
+        let Ok(input) = fs::read_to_string(get_path_for_input(day)) else {
+            if options.format.is_text() {
+                println!("Input file not found.");
+            } else {
+                eprintln!("Input file for day {day} not found.");
+            }
+            return None;
+        };
+
+        Some((solution.run)(&input, options))
+    } else {
+        Some(child_commands::run_solution(day, is_release, options).unwrap())
+    }
+}
+
+/// The solution of a day if it can run in-process, i.e. it is linked into the year binary.
+fn linked_solution<'a>(
+    solutions: &'a [Solution],
+    day: Day,
+    options: &RunOptions,
+) -> Option<&'a Solution> {
+    // a part that times out ends its process, so days are isolated in child processes then.
+    // the heap profiler needs the solution's own allocator, which only its binary installs.
+    solutions
+        .iter()
+        .find(|s| s.day == day)
+        .filter(|_| options.timeout.is_none() && !options.memory)
+}
+
+/// Output and results of a day that ran with captured output.
+struct BufferedDay {
+    day: Day,
+    stdout: String,
+    stderr: String,
+    results: Option<Vec<PartResult>>,
+    elapsed: Duration,
+}
+
+/// Run days on `options.jobs` worker threads.
+/// Linked days run in-process on the worker threads, all others as child processes.
+/// The output of each day is buffered and printed in day order, as soon as all earlier days are done.
+fn run_parallel(
+    solutions: &[Solution],
+    days: &[Day],
+    is_release: bool,
+    options: &RunOptions,
+    collect: &mut impl FnMut(Day, Vec<PartResult>),
+) {
+    // build once up front, so that the child processes do not queue up on cargo's build lock.
+    let needs_children = days.iter().any(|&day| {
+        linked_solution(solutions, day, options).is_none()
+            && Path::new(&get_path_for_bin(day)).exists()
+    });
+    if needs_children {
+        if let Err(e) = child_commands::build_solutions(is_release) {
+            eprintln!("failed to build solutions: {e:?}");
+        }
+    }
+
+    let timer = Instant::now();
+    let queue = Mutex::new(days.iter().copied());
+    let (sender, receiver) = mpsc::channel::<BufferedDay>();
+
+    let mut sum_of_days = Duration::ZERO;
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let sender = sender.clone();
+            let queue = &queue;
+
+            scope.spawn(move || loop {
+                let Some(day) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let _ = sender.send(run_buffered_day(solutions, day, is_release, options));
+            });
+        }
+        drop(sender);
+
+        let mut finished: BTreeMap<Day, BufferedDay> = BTreeMap::new();
+        let mut next = 0;
+
+        for buffered in receiver {
+            finished.insert(buffered.day, buffered);
+
+            while let Some(buffered) = days.get(next).and_then(|day| finished.remove(day)) {
+                if options.format.is_text() {
+                    print_day_header(buffered.day, next > 0);
+                }
+                print!("{}", buffered.stdout);
+                eprint!("{}", buffered.stderr);
+
+                sum_of_days += buffered.elapsed;
+                if let Some(results) = buffered.results {
+                    collect(buffered.day, results);
+                }
+
+                next += 1;
+            }
+        }
+    });
+
+    if !options.format.is_text() {
+        return;
+    }
+
+    let wall_millis = timer.elapsed().as_secs_f64() * 1000_f64;
+    let sum_millis = sum_of_days.as_secs_f64() * 1000_f64;
+    println!(
+        "\n{ANSI_BOLD}Total (Wall):{ANSI_RESET} {ANSI_ITALIC}{wall_millis:.2}ms{ANSI_RESET} on {} jobs, {ANSI_ITALIC}{sum_millis:.2}ms{ANSI_RESET} summed over days",
+        options.jobs
+    );
+}
+
+fn run_buffered_day(
+    solutions: &[Solution],
+    day: Day,
+    is_release: bool,
+    options: &RunOptions,
+) -> BufferedDay {
+    let timer = Instant::now();
+
+    let (stdout, stderr, results) = if !Path::new(&get_path_for_bin(day)).exists() {
+        if options.format.is_text() {
+            ("Not solved.\n".into(), String::new(), None)
+        } else {
+            (String::new(), String::new(), None)
+        }
+    } else if let Some(solution) = linked_solution(solutions, day, options) {
+        match fs::read_to_string(get_path_for_input(day)) {
+            Ok(input) => {
+                let (results, stdout) = output::capture(|| (solution.run)(&input, options));
+                (stdout, String::new(), Some(results))
+            }
+            Err(_) if options.format.is_text() => {
+                ("Input file not found.\n".into(), String::new(), None)
+            }
+            Err(_) => (
+                String::new(),
+                format!("Input file for day {day} not found.\n"),
+                None,
+            ),
+        }
     } else {
-        None
+        match child_commands::run_solution_captured(day, is_release, options) {
+            Ok((results, stdout, stderr)) => (stdout, stderr, Some(results)),
+            Err(e) => (
+                String::new(),
+                format!("failed to run day {day}: {e:?}\n"),
+                None,
+            ),
+        }
+    };
+
+    BufferedDay {
+        day,
+        stdout,
+        stderr,
+        results,
+        elapsed: timer.elapsed(),
     }
 }
 
+pub(crate) fn timing_from_results(day: Day, results: &[PartResult]) -> Timing {
+    let mut timing = Timing {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+        failures: vec![],
+        memory: vec![],
+    };
+
+    timing.failures = results
+        .iter()
+        .filter_map(|r| Some((r.part, r.failure.clone()?)))
+        .collect();
+
+    timing.memory = results
+        .iter()
+        .filter_map(|r| Some((r.part, r.memory?)))
+        .collect();
+
+    // NOTE: the parse step has no answer, it is recorded as part 0.
+    for result in results.iter().filter(|r| r.part == 0 || r.answer.is_some()) {
+        match result.part {
+            0 => timing.parse = Some(result.timing.clone()),
+            1 => timing.part_1 = Some(result.timing.clone()),
+            2 => timing.part_2 = Some(result.timing.clone()),
+            _ => continue,
+        }
+        timing.total_nanos += result.timing.mean_nanos;
+    }
+
+    timing
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
-    BrokenPipe,
     IO(io::Error),
 }
 
@@ -64,194 +336,193 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+#[must_use]
+pub fn get_path_for_input(day: Day) -> String {
+    format!("./data/inputs/{day}.txt")
+}
+
+/// Solutions that are not linked into the year binary live in isolated binaries.
+/// This module encapsulates invoking these binaries and reading back the result records they write.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        output::{colors_enabled, FORCE_COLOR_ENV},
+        runner::{PartResult, RunOptions, RECORD_FILE_ENV},
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        env, fs,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
+    /// Build all solution bins, so that running them does not need to compile anything.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
+        Command::new("cargo").args(&args).status()?;
+        Ok(())
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    /// Run the solution bin for a given day
+    pub fn run_solution(
+        day: Day,
+        is_release: bool,
+        options: &RunOptions,
+    ) -> Result<Vec<PartResult>, Error> {
+        let record_path = record_path(day)?;
+
+        solution_command(day, is_release, options)
+            .env(RECORD_FILE_ENV, &record_path)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?;
+
+        read_records(&record_path)
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    /// Run the solution bin for a given day, capturing its output.
+    /// Returns the results along with stdout and stderr.
+    /// The output keeps its colors if they are enabled for this process, but has no progress output.
+    pub fn run_solution_captured(
+        day: Day,
+        is_release: bool,
+        options: &RunOptions,
+    ) -> Result<(Vec<PartResult>, String, String), Error> {
+        let record_path = record_path(day)?;
+
+        let mut cmd = solution_command(day, is_release, options);
+        if colors_enabled() {
+            cmd.env(FORCE_COLOR_ENV, "1");
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let output = cmd.env(RECORD_FILE_ENV, &record_path).output()?;
 
-        Ok(output)
+        Ok((
+            read_records(&record_path)?,
+            String::from_utf8_lossy(&output.stdout).into_owned(),
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    fn solution_command(day: Day, is_release: bool, options: &RunOptions) -> Command {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            day.to_string(),
+        ];
+
+        if options.memory {
+            // NOTE: mirrors `cargo solve --dhat`.
+            args.extend([
+                "--profile".to_string(),
+                "dhat".to_string(),
+                "--features".to_string(),
+                "dhat-heap".to_string(),
+            ]);
+        } else if is_release {
+            args.push("--release".to_string());
+        }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        args.push("--".to_string());
+        args.extend(options.params.to_args());
+        args.extend(options.format.to_args());
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        if let Some(timeout) = options.timeout {
+            args.push("--timeout".to_string());
+            args.push(timeout.as_millis().to_string());
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        if options.timed {
+            // mirror `--time` flag and bench settings to child invocations.
+            args.push("--time".to_string());
+            args.extend(options.bench.to_args());
+        }
 
-                timings.total_nanos += nanos;
-            });
+        if options.verify {
+            // mirror `--verify` and `--store` flags to child invocations.
+            args.push("--verify".to_string());
+            if options.store_answers {
+                args.push("--store".to_string());
+            }
+        }
 
-        timings
+        let mut cmd = Command::new("cargo");
+        cmd.args(args);
+        cmd
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn record_path(day: Day) -> Result<PathBuf, Error> {
+        let record_path = env::temp_dir().join(format!("aoc-{}-{day}.jsonl", process::id()));
+        match fs::remove_file(&record_path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(record_path),
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn read_records(record_path: &Path) -> Result<Vec<PartResult>, Error> {
+        let records = match fs::read_to_string(record_path) {
+            Ok(records) => records,
+            // the child did not get to run any part, e.g. because it failed to compile.
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        fs::remove_file(record_path)?;
+
+        Ok(parse_records(&records))
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    pub fn parse_records(records: &str) -> Vec<PartResult> {
+        records
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| match l.parse() {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    eprintln!("Could not parse result record: {e}");
+                    None
+                }
+            })
+            .collect()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{super::timing_from_results, parse_records};
 
         use crate::day;
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn parses_result_records() {
+            let res = parse_records(concat!(
+                r#"{ "day": "01", "part": 0, "answer": null, "timing": { "mean_nanos": 10, "median_nanos": 10, "min_nanos": 10, "max_nanos": 10, "samples": 1 }, "verdict": null }"#,
+                "\n",
+                r#"{ "day": "01", "part": 1, "answer": "0", "timing": { "mean_nanos": 74.13, "median_nanos": 74, "min_nanos": 70, "max_nanos": 80, "samples": 100000 }, "verdict": "pass" }"#,
+                "\n",
+                r#"{ "day": "01", "part": 2, "answer": null, "timing": { "mean_nanos": 5, "median_nanos": 5, "min_nanos": 5, "max_nanos": 5, "samples": 1 }, "verdict": null }"#,
+                "\n\n",
+            ));
+            assert_eq!(res.len(), 3);
+            assert_eq!(res[1].answer.as_deref(), Some("0"));
+            assert_eq!(res[1].timing.samples, 100_000);
+            assert_eq!(res[2].answer, None);
+
+            let timing = timing_from_results(day!(1), &res);
+            assert_eq!(timing.parse.unwrap().mean_nanos, 10_f64);
+            assert_eq!(timing.part_1.unwrap().mean_nanos, 74.13_f64);
+            assert_eq!(timing.part_2.is_none(), true);
+            assert_eq!(timing.total_nanos, 84.13_f64);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+        fn skips_malformed_records() {
+            let res = parse_records("Part 1: 0 (74.13ns @ 100000 samples)\n{}\n");
+            assert_eq!(res.is_empty(), true);
         }
     }
 }
@@ -1,65 +1,672 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::fs::OpenOptions;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::aoc_client::{AocClient, SubmitVerdict};
+use crate::template::output::{self, out, outln, OutputFormat};
+use crate::template::timings::{Failure, MemoryStats, PartTiming};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, Day, InputSource, Params, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// Options that control how a solution part is run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub timed: bool,
+    /// Compare the result with the accepted answer in `data/answers.json`.
+    pub verify: bool,
+    /// When verifying, record results for parts that have no accepted answer yet.
+    pub store_answers: bool,
+    /// How to bench the part when `timed` is set.
+    pub bench: BenchConfig,
+    /// Parameters that override the defaults of the solution.
+    pub params: Params,
+    /// Where the solution binary reads its input from.
+    pub input: InputSource,
+    /// Wall-clock time after which a part is reported as timed out.
+    pub timeout: Option<Duration>,
+    /// Number of days that `run_multi` runs concurrently, `0` and `1` run them one after another.
+    pub jobs: usize,
+    /// The variant of a solution binary to run, `None` runs the default one (see [`Variant`]).
+    pub variant: Option<String>,
+    /// Run every variant of a solution binary and compare them (see [`run_variants`]).
+    pub compare_variants: bool,
+    /// Build solutions with the `dhat-heap` feature and report the heap usage of each part.
+    pub memory: bool,
+    /// How results are printed, structured formats print a record per part instead of text.
+    pub format: OutputFormat,
+}
+
+impl RunOptions {
+    /// Read the options from the flags passed to a solution binary.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .and_then(|x| x.parse::<u64>().ok())
+        };
+
+        Self {
+            timed: args.contains(&"--time".into()),
+            verify: args.contains(&"--verify".into()),
+            store_answers: args.contains(&"--store".into()),
+            bench: BenchConfig {
+                budget: value_of("--budget")
+                    .map_or(BenchConfig::default().budget, Duration::from_millis),
+                iterations: value_of("--iterations"),
+            },
+            params: args
+                .windows(2)
+                .filter(|pair| pair[0] == "--param")
+                .map(|pair| or_exit(Params::parse_pair(&pair[1])))
+                .collect(),
+            input: or_exit(InputSource::from_args(&args)),
+            timeout: value_of("--timeout").map(Duration::from_millis),
+            // a solution binary runs a single day.
+            jobs: 1,
+            variant: args
+                .iter()
+                .position(|x| x == "--variant")
+                .and_then(|i| args.get(i + 1))
+                .cloned(),
+            compare_variants: args.contains(&"--compare".into()),
+            // heap usage is measured by the solution binary if it was built with `dhat-heap`.
+            memory: cfg!(feature = "dhat-heap"),
+            format: args
+                .iter()
+                .position(|x| x == "--format")
+                .and_then(|i| args.get(i + 1))
+                .map(|format| or_exit(format.parse()))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether intermediate output is printed while a part runs, and overwritten with its result.
+    /// This is only useful for text output on a terminal.
+    #[must_use]
+    pub fn shows_progress(&self) -> bool {
+        self.format.is_text() && output::is_terminal() && !output::is_capturing()
+    }
+
+    /// Whether results answer the puzzle, i.e. the puzzle input is used with default parameters.
+    /// Only these results are verified and submitted.
+    #[must_use]
+    pub fn answers_puzzle(&self) -> bool {
+        self.input == InputSource::Puzzle && self.params.is_empty()
+    }
+}
+
+/// Controls how many measured runs are done when benching a part.
+#[derive(Clone, Copy, Debug)]
+pub struct BenchConfig {
+    /// Approximate execution time to spend on measured runs (at least 10 runs are done).
+    /// A tenth of the budget is spent on warm-up runs beforehand.
+    pub budget: Duration,
+    /// A fixed number of measured runs, takes precedence over `budget`.
+    pub iterations: Option<u64>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            iterations: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// The flags that pass this config on to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--budget".into(), self.budget.as_millis().to_string()];
+
+        if let Some(iterations) = self.iterations {
+            args.push("--iterations".into());
+            args.push(iterations.to_string());
+        }
+
+        args
+    }
+}
+
+/// If set, solution binaries and year binaries append a JSON record for each part to the file at this path.
+pub const RECORD_FILE_ENV: &str = "AOC_RECORD_FILE";
+
+/// The outcome of running a single solution part.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub timing: PartTiming,
+    pub verdict: Option<Verdict>,
+    /// Set if the part timed out or panicked.
+    pub failure: Option<Failure>,
+    /// Heap usage of the first run, set if the solution was built with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+/// A day that is linked into the year binary, so that it can be run without spawning a child process.
+/// Each solution registers itself through the [`crate::solution`] macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    pub run: fn(&str, &RunOptions) -> Vec<PartResult>,
+}
+
+/// Name of the variant made up of `part_one` and `part_two`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// An implementation of a day in a solution binary.
+/// Alternate implementations are declared with `variants = [...]` in the [`crate::solution`] macro,
+/// and are run with `--variant <name>` or compared with `--compare`.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub name: &'static str,
+    pub run: fn(&str, &RunOptions) -> Vec<PartResult>,
+}
+
+/// The return type of a solution part, either `Option<T>` or `Result<Option<T>, E>`.
+/// Errors are printed next to the part instead of panicking, e.g. for malformed input.
+pub trait PartOutput {
+    type Answer: Display;
+
+    fn into_result(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<Option<T>, E> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/// A solution part, either `fn(input) -> R` or `fn(input, &Params) -> R` where `R` is a [`PartOutput`].
+/// The marker `M` only tells the two shapes apart, it is inferred from the function.
+pub trait PartFn<I, M> {
+    type Output: Display;
+
+    fn call(&self, input: I, params: &Params) -> Result<Option<Self::Output>, String>;
+}
+
+impl<I, R: PartOutput, F: Fn(I) -> R> PartFn<I, ()> for F {
+    type Output = R::Answer;
+
+    fn call(&self, input: I, _: &Params) -> Result<Option<R::Answer>, String> {
+        self(input).into_result()
+    }
+}
+
+impl<I, R: PartOutput, F: Fn(I, &Params) -> R> PartFn<I, (Params,)> for F {
+    type Output = R::Answer;
+
+    fn call(&self, input: I, params: &Params) -> Result<Option<R::Answer>, String> {
+        self(input, params).into_result()
+    }
+}
+
+/// Read the input of a solution binary from the source selected in `options`.
+/// Parameters declared by an example are added to `options`, unless they were passed on the command line.
+/// Exits with an error message if the input can not be read.
+pub fn read_input(day: Day, options: &mut RunOptions) -> String {
+    match options.input.read(day) {
+        Ok((input, params)) => {
+            options.params.set_defaults(params);
+            input
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The value of a flag passed to a solution binary, or print why it is malformed and exit, like [`read_input`].
+fn or_exit<T>(result: Result<T, String>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Run the variant of a solution binary that was selected with `--variant`, or compare all of them with `--compare`.
+pub fn run_variants(variants: &[Variant], input: &str, day: Day, options: &RunOptions) {
+    if options.compare_variants {
+        compare_variants(variants, input, options);
+        return;
+    }
+
+    let name = options.variant.as_deref().unwrap_or(DEFAULT_VARIANT);
+    let Some(variant) = variants.iter().find(|variant| variant.name == name) else {
+        let names: Vec<&str> = variants.iter().map(|variant| variant.name).collect();
+        eprintln!(
+            "Unknown variant `{name}`, expected one of: {}.",
+            names.join(", ")
+        );
+        process::exit(1);
+    };
+
+    for result in (variant.run)(input, options) {
+        write_record(&result);
+
+        if let (Some(answer), true) = (result.answer, options.answers_puzzle()) {
+            submit_result(answer, day, result.part);
+        }
+    }
+}
+
+/// Bench every variant on the same input, then print the answers and timings of each part side by side.
+/// Exits with an error if the variants do not agree on an answer.
+/// The comparison is a text report, `--format` does not apply to it.
+fn compare_variants(variants: &[Variant], input: &str, options: &RunOptions) {
+    let options = RunOptions {
+        timed: true,
+        format: OutputFormat::Text,
+        ..options.clone()
+    };
+
+    let runs: Vec<(&str, Vec<PartResult>)> = variants
+        .iter()
+        .map(|variant| {
+            println!("{ANSI_BOLD}Variant {}{ANSI_RESET}", variant.name);
+            println!("------");
+            let results = (variant.run)(input, &options);
+            println!();
+            (variant.name, results)
+        })
+        .collect();
+
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    let mut parts: Vec<u8> = runs
+        .iter()
+        .flat_map(|(_, results)| results.iter().map(|result| result.part))
+        .collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    let mut agree = true;
+
+    for part in parts {
+        let results: Vec<(&str, &PartResult)> = runs
+            .iter()
+            .filter_map(|(name, results)| Some((*name, results.iter().find(|r| r.part == part)?)))
+            .collect();
+
+        let label = if part == 0 {
+            "Parse".to_string()
+        } else {
+            format!("Part {part}")
+        };
+
+        let timings: Vec<String> = results
+            .iter()
+            .map(|(name, result)| format!("{name} {:.1?}", result.timing.mean()))
+            .collect();
+
+        let answers: Vec<Option<&str>> = results
+            .iter()
+            .map(|(_, result)| result.answer.as_deref())
+            .collect();
+
+        if part == 0 {
+            println!("{label}: {}", timings.join(" · "));
+        } else if answers.windows(2).any(|pair| pair[0] != pair[1]) {
+            agree = false;
+            let answers: Vec<String> = results
+                .iter()
+                .zip(&answers)
+                .map(|((name, _), answer)| format!("{name} {}", answer.unwrap_or("✖")))
+                .collect();
+            println!("{label}: ✖ {ANSI_RED}{}{ANSI_RESET}", answers.join(" · "));
+        } else {
+            let answer = answers.first().copied().flatten().unwrap_or("✖");
+            println!(
+                "{label}: {ANSI_BOLD}{answer}{ANSI_RESET} · {}",
+                timings.join(" · ")
+            );
+        }
+    }
+
+    if !agree {
+        eprintln!("\nVariants do not agree on all answers.");
+        process::exit(1);
+    }
+}
+
+/// Run a solution part with the given options and print the result.
+pub fn run_part_with<I: Clone, M>(
+    func: impl PartFn<I, M>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let bench_config = options.timed.then_some(&options.bench);
+    let watchdog = Watchdog::start(options.timeout, day, part, options.format);
+    let func = |input| func.call(input, &options.params);
+    let profiler = HeapProfiler::new(day, part);
+    let (result, timing, memory) = run_timed(
+        func,
+        input,
+        bench_config,
+        watchdog,
+        profiler,
+        options.shows_progress(),
+        |result| print_result(result, &part_str, ""),
+    );
+
+    let failure = result.as_ref().err().cloned();
+    let result = result.unwrap_or_else(|failure| Err(failure.to_string()));
+
+    if options.format.is_text() {
+        print_result(&result, &part_str, &format_duration(&timing));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        if timing.samples > 1 {
+            print_statistics(&timing);
+        }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        if let Some(memory) = &memory {
+            print_memory(memory);
+        }
     }
+
+    let answer = result
+        .as_ref()
+        .ok()
+        .and_then(Option::as_ref)
+        .map(ToString::to_string);
+
+    let verdict = (options.verify && options.answers_puzzle())
+        .then(|| verify_result(answer.as_deref(), day, part, options));
+
+    let result = PartResult {
+        day,
+        part,
+        answer,
+        timing,
+        verdict,
+        failure,
+        memory,
+    };
+
+    options.format.print_record(&result);
+    result
+}
+
+/// Run the shared `parse` function of a solution and print its timing.
+/// The timing is reported as part `0`, it carries neither an answer nor a verdict.
+pub fn run_parse_with<P>(
+    func: impl Fn(&str) -> P,
+    input: &str,
+    day: Day,
+    options: &RunOptions,
+) -> (Option<P>, PartResult) {
+    let bench_config = options.timed.then_some(&options.bench);
+    let watchdog = Watchdog::start(options.timeout, day, 0, options.format);
+    let profiler = HeapProfiler::new(day, 0);
+    let (parsed, timing, memory) = run_timed(
+        func,
+        input,
+        bench_config,
+        watchdog,
+        profiler,
+        options.shows_progress(),
+        |_| out!("Parse"),
+    );
+
+    if options.format.is_text() {
+        clear_line();
+        match &parsed {
+            Ok(_) => outln!("Parse{}", format_duration(&timing)),
+            Err(failure) => outln!("Parse: ✖ {ANSI_RED}{failure}{ANSI_RESET}"),
+        }
+
+        if timing.samples > 1 {
+            print_statistics(&timing);
+        }
+
+        if let Some(memory) = &memory {
+            print_memory(memory);
+        }
+    }
+
+    let (parsed, failure) = match parsed {
+        Ok(parsed) => (Some(parsed), None),
+        Err(failure) => (None, Some(failure)),
+    };
+
+    let result = PartResult {
+        day,
+        part: 0,
+        answer: None,
+        timing,
+        verdict: None,
+        failure,
+        memory,
+    };
+
+    options.format.print_record(&result);
+    (parsed, result)
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (see [`BenchConfig`], by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// A panic in the first run is caught and returned as a failure, the part is not benched then.
+/// The watchdog and the heap profiler only cover the first run, bench runs take about as long.
+/// With `progress`, `hook` prints the result of the first run while the part is benched.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<&BenchConfig>,
+    watchdog: Watchdog,
+    profiler: HeapProfiler,
+    progress: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (Result<T, Failure>, PartTiming, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
+        let profiler = profiler.start();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+        (result, profiler.stop())
     };
     let base_time = timer.elapsed();
+    watchdog.stop();
 
-    hook(&result);
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            let failure = Failure::Panicked(panic_message(payload.as_ref()));
+            return (Err(failure), PartTiming::from_samples(&[base_time]), memory);
+        }
+    };
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if progress {
+        hook(&result);
+    }
+
+    let timing = if let Some(config) = bench_config {
+        bench(func, input, &base_time, config, progress)
     } else {
-        (base_time, 1)
+        PartTiming::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (Ok(result), timing, memory)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown reason".into())
+}
+
+/// Reports a part as timed out and exits the process if it does not finish in time.
+/// A part that hangs can not be stopped from another thread, ending the process is the only way out.
+/// This is why `run_multi` runs days as child processes when a timeout is set.
+struct Watchdog(Option<(mpsc::Sender<()>, JoinHandle<()>)>);
+
+impl Watchdog {
+    fn start(timeout: Option<Duration>, day: Day, part: u8, format: OutputFormat) -> Self {
+        let Some(timeout) = timeout else {
+            return Self(None);
+        };
+
+        let (finished, finished_rx) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = finished_rx.recv_timeout(timeout) {
+                let failure = Failure::Timeout(timeout);
+                let label = if part == 0 {
+                    "Parse".into()
+                } else {
+                    format!("Part {part}")
+                };
+
+                if format.is_text() {
+                    clear_line();
+                    println!("{label}: ✖ {ANSI_RED}{failure}{ANSI_RESET}");
+                }
+
+                let result = PartResult {
+                    day,
+                    part,
+                    answer: None,
+                    timing: PartTiming::from_samples(&[timeout]),
+                    verdict: None,
+                    failure: Some(failure),
+                    memory: None,
+                };
+
+                format.print_record(&result);
+                write_record(&result);
+
+                process::exit(1);
+            }
+        });
+
+        Self(Some((finished, handle)))
+    }
+
+    fn stop(self) {
+        if let Some((finished, handle)) = self.0 {
+            // dropping the sender wakes up the watchdog thread.
+            drop(finished);
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Measures the heap usage of a single run with dhat, if the `dhat-heap` feature is enabled.
+/// Each run writes its own profile, e.g. `dhat-heap-01-2.json` for day 1 part 2, which can be
+/// opened in [dh_view](https://nnethercote.github.io/dh_view/dh_view.html).
+struct HeapProfiler {
+    #[cfg_attr(not(feature = "dhat-heap"), allow(dead_code))]
+    file_name: String,
+    #[cfg(feature = "dhat-heap")]
+    profiler: Option<dhat::Profiler>,
+}
+
+impl HeapProfiler {
+    fn new(day: Day, part: u8) -> Self {
+        Self {
+            file_name: format!("dhat-heap-{day}-{part}.json"),
+            #[cfg(feature = "dhat-heap")]
+            profiler: None,
+        }
+    }
+
+    fn start(self) -> Self {
+        #[cfg(feature = "dhat-heap")]
+        {
+            let profiler = dhat::Profiler::builder().file_name(&self.file_name).build();
+            Self {
+                profiler: Some(profiler),
+                ..self
+            }
+        }
+
+        #[cfg(not(feature = "dhat-heap"))]
+        self
+    }
+
+    /// Stop profiling and write the profile, returning the heap usage since [`HeapProfiler::start`].
+    fn stop(self) -> Option<MemoryStats> {
+        #[cfg(feature = "dhat-heap")]
+        {
+            let profiler = self.profiler?;
+            let stats = dhat::HeapStats::get();
+            drop(profiler);
+
+            Some(MemoryStats {
+                peak_bytes: stats.max_bytes as u64,
+                allocations: stats.total_blocks,
+                allocated_bytes: stats.total_bytes,
+            })
+        }
+
+        #[cfg(not(feature = "dhat-heap"))]
+        None
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    progress: bool,
+) -> PartTiming {
+    if progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // NOTE: slow parts are considered warm after the initial run.
+    let warmup = config.budget / 10;
+    let warmup_timer = Instant::now();
+    while warmup_timer.elapsed() + *base_time < warmup {
+        black_box(func(black_box(input.clone())));
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = config.iterations.map_or_else(
+        || (config.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000),
+        // at least one sample, e.g. for `--iterations 0` passed to a solution binary directly.
+        |iterations| u128::from(iterations.max(1)),
+    );
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,22 +678,13 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    PartTiming::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(timing: &PartTiming) -> String {
+    let duration = timing.mean();
+    let samples = timing.samples;
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -94,53 +692,143 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_statistics(timing: &PartTiming) {
+    let nanos = |x: f64| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(x as u64)
+    };
+
+    outln!(
+        "  {ANSI_ITALIC}median {:.1?} | p5 {:.1?} | p95 {:.1?} | σ {:.1?} | 95% CI ± {:.1?} | {} outliers{ANSI_RESET}",
+        nanos(timing.median_nanos),
+        nanos(timing.p5_nanos),
+        nanos(timing.p95_nanos),
+        nanos(timing.stddev_nanos),
+        nanos(timing.ci95_nanos),
+        timing.outliers,
+    );
+}
+
+/// Move back to the start of the line, to overwrite the progress output of a part.
+fn clear_line() {
+    if output::is_terminal() && !output::is_capturing() {
+        out!("\r");
+    }
+}
+
+fn print_memory(memory: &MemoryStats) {
+    outln!("  {ANSI_ITALIC}{memory}{ANSI_RESET}");
+}
+
+fn print_result<T: Display>(result: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    clear_line();
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    clear_line();
+                    outln!("{str}");
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                clear_line();
+                outln!("{part}: ✖             ");
+            }
+        }
+        Err(e) => {
+            if is_intermediate_result {
+                out!("{part}: ✖");
+            } else {
+                clear_line();
+                outln!("{part}: ✖ {ANSI_RED}{e}{ANSI_RESET}");
             }
         }
     }
 }
 
+/// Compare the result against the accepted answer in `data/answers.json`.
+/// If `options.store_answers` is set, results for parts without an accepted answer are recorded.
+fn verify_result(result: Option<&str>, day: Day, part: u8, options: &RunOptions) -> Verdict {
+    let mut answers = Answers::read_from_file();
+    let verdict = answers.verify(day, part, result);
+
+    if options.format.is_text() {
+        outln!("{}", format_verdict(part, &verdict));
+    }
+
+    if let (Verdict::Missing, Some(result), true) = (&verdict, result, options.store_answers) {
+        answers.set(day, part, result);
+        match answers.store_file() {
+            Ok(()) if options.format.is_text() => {
+                outln!("Part {part}: stored answer {ANSI_BOLD}{result}{ANSI_RESET}");
+            }
+            Ok(()) => {}
+            Err(e) => eprintln!("failed to store answer: {e}"),
+        }
+    }
+
+    verdict
+}
+
+fn format_verdict(part: u8, verdict: &Verdict) -> String {
+    format!("Part {part} verify: {verdict}")
+}
+
+/// Append the result as a JSON line to the file named by [`RECORD_FILE_ENV`], if set.
+/// This lets `run_multi` read structured results back from solutions that run as child processes,
+/// and the workspace binary read them back from year binaries.
+pub(crate) fn write_record(result: &PartResult) {
+    let Ok(path) = env::var(RECORD_FILE_ENV) else {
+        return;
+    };
+
+    let record = match JsonValue::from(result).stringify() {
+        Ok(record) => record,
+        Err(e) => {
+            eprintln!("failed to serialize result record: {e}");
+            return;
+        }
+    };
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{record}"));
+
+    if let Err(e) = written {
+        eprintln!("failed to write result record to \"{path}\": {e}");
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is set, see [`aoc_client::http::read_session`] (and `AOC_USER_AGENT` should name a contact).
+///  3. the result is not already known to be wrong.
+///
+/// The verdict is recorded in `data/answers.json`.
+fn submit_result(result: String, day: Day, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +844,201 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let mut answers = Answers::read_from_file();
+
+    if let Err(rejection) = answers.check_submission(day, part, &result) {
+        eprintln!("Not submitting {result}: {rejection}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+
+    let verdict = match aoc_client::client().submit(day, part, &result) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!("failed to submit: {e}");
+            process::exit(1);
+        }
+    };
+
+    match verdict {
+        SubmitVerdict::Correct => answers.set(day, part, &result),
+        SubmitVerdict::Incorrect(hint) => answers.add_wrong(day, part, &result, hint),
+        SubmitVerdict::TooRecent | SubmitVerdict::WrongLevel | SubmitVerdict::Unknown => return,
+    }
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("failed to record submission: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+        map.insert(
+            "verdict".into(),
+            value
+                .verdict
+                .as_ref()
+                .map_or(JsonValue::Null, |v| JsonValue::String(v.to_string())),
+        );
+
+        if let Some(failure) = &value.failure {
+            map.insert("failure".into(), JsonValue::String(failure.to_string()));
+        }
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected record.timing to be present.")
+            .map(PartTiming::try_from)??;
+
+        let verdict = match json.get("verdict") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.verdict to be null or string.")?
+                    .parse()?,
+            ),
+            _ => None,
+        };
+
+        let failure = match json.get("failure") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.failure to be null or string.")?
+                    .parse()?,
+            ),
+            _ => None,
+        };
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartResult {
+            day,
+            part,
+            answer: answer.cloned(),
+            timing,
+            verdict,
+            failure,
+            memory,
+        })
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("result record is not valid JSON."))?;
+        PartResult::try_from(&json)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartResult;
+    use crate::{
+        day,
+        template::{
+            answers::Verdict,
+            timings::{Failure, MemoryStats, PartTiming},
+        },
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn round_trips_result_records() {
+        let result = PartResult {
+            day: day!(3),
+            part: 2,
+            answer: Some("Part 1: @ (2s @ 5 samples)".into()),
+            timing: PartTiming::from_samples(&[Duration::from_nanos(74), Duration::from_nanos(76)]),
+            verdict: Some(Verdict::Fail {
+                expected: "10".into(),
+            }),
+            failure: Some(Failure::Panicked("index out of bounds".into())),
+            memory: Some(MemoryStats {
+                peak_bytes: 2048,
+                allocations: 12,
+                allocated_bytes: 4096,
+            }),
+        };
+
+        let record = JsonValue::from(&result).stringify().unwrap();
+        let parsed: PartResult = record.parse().unwrap();
+
+        assert_eq!(parsed.day, day!(3));
+        assert_eq!(parsed.part, 2);
+        assert_eq!(parsed.answer, result.answer);
+        assert_eq!(parsed.timing, result.timing);
+        assert_eq!(parsed.verdict, result.verdict);
+        assert_eq!(parsed.failure, result.failure);
+        assert_eq!(parsed.memory, result.memory);
+    }
+
+    #[test]
+    fn parses_missing_answers() {
+        let parsed: PartResult = r#"{ "day": "01", "part": 1, "answer": null, "timing": { "mean_nanos": 10, "median_nanos": 10, "min_nanos": 10, "max_nanos": 10, "samples": 1 }, "verdict": null }"#
+            .parse()
+            .unwrap();
+
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.verdict, None);
+        assert_eq!(parsed.failure, None);
+        assert_eq!(parsed.memory, None);
+        assert_eq!(parsed.timing.samples, 1);
+    }
 }
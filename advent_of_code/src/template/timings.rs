@@ -1,8 +1,10 @@
 use std::{
     collections::HashMap,
+    fmt::Display,
     fs,
     io::{Error, ErrorKind},
     str::FromStr,
+    time::Duration,
 };
 use tinyjson::JsonValue;
 
@@ -14,9 +16,210 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Time spent in the shared `parse` function, for solutions that declare one.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Parts that timed out or panicked, by part number (`0` is the parse step).
+    pub failures: Vec<(u8, Failure)>,
+    /// Heap usage of each part, by part number (`0` is the parse step). Only measured by `time --memory`.
+    pub memory: Vec<(u8, MemoryStats)>,
+}
+
+impl Timing {
+    fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            0 => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Why a part did not produce a result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part did not finish within the timeout.
+    Timeout(Duration),
+    /// The part panicked, with the panic message.
+    Panicked(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout(timeout) => write!(f, "timeout after {}ms", timeout.as_millis()),
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(message) = s.strip_prefix("panicked: ") {
+            return Ok(Failure::Panicked(message.to_string()));
+        }
+
+        s.strip_prefix("timeout after ")
+            .and_then(|ms| ms.strip_suffix("ms"))
+            .and_then(|ms| ms.parse().ok())
+            .map(|ms| Failure::Timeout(Duration::from_millis(ms)))
+            .ok_or(format!("unknown failure: {s}"))
+    }
+}
+
+/// Heap usage of a single run of a part, as measured by dhat (see the `dhat-heap` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated at the point of peak heap usage.
+    pub peak_bytes: u64,
+    /// Number of allocations over the entire run.
+    pub allocations: u64,
+    /// Bytes allocated over the entire run.
+    pub allocated_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {} | {} allocations | {} allocated",
+            format_bytes(self.peak_bytes),
+            self.allocations,
+            format_bytes(self.allocated_bytes)
+        )
+    }
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Represents benchmark statistics for a single part, in raw nanoseconds.
+/// Outliers are discarded before computing the statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub p5_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci95_nanos: f64,
+    pub samples: u64,
+    pub outliers: u64,
+}
+
+impl PartTiming {
+    /// Summarize the durations of a set of runs.
+    /// Samples outside of Tukey's fences (1.5 × IQR beyond the quartiles) are counted as outliers and discarded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let total = nanos.len();
+        nanos.retain(|x| (low..=high).contains(x));
+        let count = nanos.len();
+
+        let mean_nanos = nanos.iter().sum::<f64>() / count.max(1) as f64;
+        let stddev_nanos = if count > 1 {
+            let variance =
+                nanos.iter().map(|x| (x - mean_nanos).powi(2)).sum::<f64>() / (count - 1) as f64;
+            variance.sqrt()
+        } else {
+            0_f64
+        };
+
+        PartTiming {
+            mean_nanos,
+            median_nanos: percentile(&nanos, 0.5),
+            min_nanos: nanos.first().copied().unwrap_or_default(),
+            max_nanos: nanos.last().copied().unwrap_or_default(),
+            p5_nanos: percentile(&nanos, 0.05),
+            p95_nanos: percentile(&nanos, 0.95),
+            stddev_nanos,
+            ci95_nanos: 1.96 * stddev_nanos / (count.max(1) as f64).sqrt(),
+            samples: count as u64,
+            outliers: (total - count) as u64,
+        }
+    }
+
+    /// The mean duration of a run.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.mean_nanos as u64)
+    }
+}
+
+/// Linearly interpolated percentile of sorted values, `p` in `0.0..=1.0`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0_f64;
+    }
+
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Change in median run time of a single part between a baseline and a new run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingDelta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl TimingDelta {
+    #[must_use]
+    pub fn change_nanos(&self) -> f64 {
+        self.current_nanos - self.baseline_nanos
+    }
+
+    /// Relative change against the baseline, e.g. `25.0` if the part got a quarter slower.
+    #[must_use]
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_nanos == 0_f64 {
+            0_f64
+        } else {
+            self.change_nanos() / self.baseline_nanos * 100_f64
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -52,11 +255,18 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory figures of `self` are kept for days that `other` did not measure memory for.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.memory.is_empty() {
+                if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                    timing.memory.clone_from(&stored.memory);
+                }
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -69,6 +279,30 @@ impl Timings {
         Timings { data }
     }
 
+    /// Merge the memory figures of `new` into `self`, keeping the run times of `self`.
+    /// Days without stored timings are added without run times, as profiled runs are not representative.
+    pub fn merge_memory(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for timing in &new.data {
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(stored) => stored.memory.clone_from(&timing.memory),
+                None => data.push(Timing {
+                    day: timing.day,
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    failures: vec![],
+                    memory: timing.memory.clone(),
+                }),
+            }
+        }
+
+        data.sort_unstable_by_key(|timing| timing.day);
+        Timings { data }
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -79,6 +313,31 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Compare `current` against `self` as the baseline, for all parts that are present in both.
+    pub fn compare(&self, current: &Self) -> Vec<TimingDelta> {
+        let mut deltas: Vec<TimingDelta> = vec![];
+
+        for timing in &current.data {
+            let Some(baseline) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for part in 0..=2 {
+                if let (Some(old), Some(new)) = (baseline.part(part), timing.part(part)) {
+                    deltas.push(TimingDelta {
+                        day: timing.day,
+                        part,
+                        baseline_nanos: old.median_nanos,
+                        current_nanos: new.median_nanos,
+                    });
+                }
+            }
+        }
+
+        deltas.sort_unstable_by_key(|d| (d.day, d.part));
+        deltas
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -128,25 +387,54 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::from(parse));
+        }
 
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
+        if !value.failures.is_empty() {
+            map.insert(
+                "failures".into(),
+                JsonValue::Object(
+                    value
+                        .failures
+                        .iter()
+                        .map(|(part, failure)| {
+                            (part.to_string(), JsonValue::String(failure.to_string()))
+                        })
+                        .collect(),
+                ),
+            );
+        }
+
+        if !value.memory.is_empty() {
+            map.insert(
+                "memory".into(),
+                JsonValue::Object(
+                    value
+                        .memory
+                        .iter()
+                        .map(|(part, stats)| (part.to_string(), JsonValue::from(stats)))
+                        .collect(),
+                ),
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -165,26 +453,218 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let parse = json.get("parse").map_or(Ok(None), parse_part_timing)?;
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .map(parse_part_timing)
+            .ok_or("Expected timing.part_1 to be null, string or object.")??;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .map(parse_part_timing)
+            .ok_or("Expected timing.part_2 to be null, string or object.")??;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let mut failures = match json.get("failures") {
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.failures to be an object.")?
+                .iter()
+                .map(|(part, failure)| {
+                    let part = part
+                        .parse()
+                        .or(Err("Expected timing.failures keys to be part numbers."))?;
+                    let failure = failure
+                        .get::<String>()
+                        .ok_or("Expected timing.failures values to be strings.")?
+                        .parse()?;
+                    Ok((part, failure))
+                })
+                .collect::<Result<Vec<_>, String>>()?,
+            None => vec![],
+        };
+        failures.sort_unstable_by_key(|(part, _)| *part);
+
+        let mut memory = match json.get("memory") {
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.memory to be an object.")?
+                .iter()
+                .map(|(part, stats)| {
+                    let part = part
+                        .parse()
+                        .or(Err("Expected timing.memory keys to be part numbers."))?;
+                    Ok((part, MemoryStats::try_from(stats)?))
+                })
+                .collect::<Result<Vec<_>, String>>()?,
+            None => vec![],
+        };
+        memory.sort_unstable_by_key(|(part, _)| *part);
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            failures,
+            memory,
+        })
+    }
+}
+
+fn parse_part_timing(value: &JsonValue) -> Result<Option<PartTiming>, String> {
+    match value {
+        JsonValue::Null => Ok(None),
+        // NOTE: older timing files stored the formatted mean duration, e.g. `"74.13ns"`.
+        JsonValue::String(s) => parse_legacy_duration(s)
+            .map(|nanos| {
+                Some(PartTiming {
+                    mean_nanos: nanos,
+                    median_nanos: nanos,
+                    min_nanos: nanos,
+                    max_nanos: nanos,
+                    p5_nanos: nanos,
+                    p95_nanos: nanos,
+                    stddev_nanos: 0_f64,
+                    ci95_nanos: 0_f64,
+                    samples: 1,
+                    outliers: 0,
+                })
+            })
+            .ok_or(format!("Could not parse duration from: {s}")),
+        value => PartTiming::try_from(value).map(Some),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+fn parse_legacy_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("p5_nanos".into(), JsonValue::Number(value.p5_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+        map.insert("ci95_nanos".into(), JsonValue::Number(value.ci95_nanos));
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part_timing.{key} to be a number."))
+        };
+
+        // NOTE: the spread statistics were added later, fall back to the central values if they are missing.
+        let optional = |key: &str, default: f64| match json.get(key) {
+            Some(_) => number(key),
+            None => Ok(default),
+        };
+
+        let mean_nanos = number("mean_nanos")?;
+        let median_nanos = number("median_nanos")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartTiming {
+            mean_nanos,
+            median_nanos,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            p5_nanos: optional("p5_nanos", median_nanos)?,
+            p95_nanos: optional("p95_nanos", median_nanos)?,
+            stddev_nanos: optional("stddev_nanos", 0_f64)?,
+            ci95_nanos: optional("ci95_nanos", 0_f64)?,
+            samples: number("samples")? as u64,
+            outliers: optional("outliers", 0_f64)? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+            map.insert(
+                "allocated_bytes".into(),
+                JsonValue::Number(value.allocated_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+            allocated_bytes: number("allocated_bytes")?,
         })
     }
 }
@@ -195,35 +675,66 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    fn mock_part(nanos: f64) -> PartTiming {
+        PartTiming {
+            mean_nanos: nanos,
+            median_nanos: nanos,
+            min_nanos: nanos,
+            max_nanos: nanos,
+            p5_nanos: nanos,
+            p95_nanos: nanos,
+            stddev_nanos: 0_f64,
+            ci95_nanos: 0_f64,
+            samples: 10,
+            outliers: 0,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(mock_part(10e+6)),
+                    part_2: Some(mock_part(20e+6)),
                     total_nanos: 3e+10,
+                    failures: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(mock_part(30e+6)),
+                    part_2: Some(mock_part(40e+6)),
                     total_nanos: 7e+10,
+                    failures: vec![],
+                    memory: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(mock_part(40e+6)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    failures: vec![],
+                    memory: vec![],
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::timings::{Failure, MemoryStats, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -232,11 +743,26 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1.as_ref().unwrap().mean_nanos, 1_000_000_f64);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_part_timing_objects() {
+            let json = r#"{ "data": [{ "day": "01", "parse": { "mean_nanos": 200, "median_nanos": 200, "min_nanos": 200, "max_nanos": 200, "samples": 1 }, "part_1": { "mean_nanos": 1500, "median_nanos": 1400, "min_nanos": 1000, "max_nanos": 3000, "samples": 100 }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.median_nanos, 1400_f64);
+            assert_eq!(part_1.min_nanos, 1000_f64);
+            assert_eq!(part_1.max_nanos, 3000_f64);
+            assert_eq!(part_1.samples, 100);
+            assert_eq!(
+                timings.data[0].parse.as_ref().unwrap().median_nanos,
+                200_f64
+            );
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -244,6 +770,50 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn handles_failures() {
+            let json = r#"{ "data": [{ "day": "03", "part_1": null, "part_2": null, "total_nanos": 0, "failures": { "2": "panicked: index out of bounds", "1": "timeout after 5000ms" } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            assert_eq!(
+                timings.data[0].failures,
+                vec![
+                    (1, Failure::Timeout(Duration::from_secs(5))),
+                    (2, Failure::Panicked("index out of bounds".into()))
+                ]
+            );
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert_eq!(
+                Timings::try_from(json).unwrap().data[0].failures,
+                timings.data[0].failures
+            );
+        }
+
+        #[test]
+        fn handles_memory() {
+            let json = r#"{ "data": [{ "day": "05", "part_1": null, "part_2": null, "total_nanos": 0, "memory": { "1": { "peak_bytes": 2048, "allocations": 3, "allocated_bytes": 4096 } } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            assert_eq!(
+                timings.data[0].memory,
+                vec![(
+                    1,
+                    MemoryStats {
+                        peak_bytes: 2048,
+                        allocations: 3,
+                        allocated_bytes: 4096,
+                    }
+                )]
+            );
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert_eq!(
+                Timings::try_from(json).unwrap().data[0].memory,
+                timings.data[0].memory
+            );
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -259,6 +829,53 @@ mod tests {
         }
     }
 
+    mod part_timing {
+        use std::time::Duration;
+
+        use crate::template::timings::PartTiming;
+
+        #[test]
+        fn summarizes_samples() {
+            let part = PartTiming::from_samples(&[
+                Duration::from_nanos(40),
+                Duration::from_nanos(10),
+                Duration::from_nanos(30),
+                Duration::from_nanos(20),
+            ]);
+            assert_eq!(part.mean_nanos, 25_f64);
+            assert_eq!(part.median_nanos, 25_f64);
+            assert_eq!(part.min_nanos, 10_f64);
+            assert_eq!(part.max_nanos, 40_f64);
+            assert_eq!(part.samples, 4);
+            assert_eq!(part.outliers, 0);
+        }
+
+        #[test]
+        fn discards_outliers() {
+            let mut samples: Vec<Duration> = (100..=119).map(Duration::from_nanos).collect();
+            samples.push(Duration::from_nanos(10_000));
+            let part = PartTiming::from_samples(&samples);
+            assert_eq!(part.samples, 20);
+            assert_eq!(part.outliers, 1);
+            assert_eq!(part.max_nanos, 119_f64);
+            assert_eq!(part.mean_nanos, 109.5_f64);
+            assert_eq!(part.median_nanos, 109.5_f64);
+            assert!((part.p5_nanos - 100.95).abs() < 1e-9);
+            assert!((part.p95_nanos - 118.05).abs() < 1e-9);
+            assert!((part.stddev_nanos - 5.916_079_783).abs() < 1e-6);
+            assert!((part.ci95_nanos - 1.96 * part.stddev_nanos / 20_f64.sqrt()).abs() < 1e-9);
+        }
+
+        #[test]
+        fn handles_single_sample() {
+            let part = PartTiming::from_samples(&[Duration::from_nanos(42)]);
+            assert_eq!(part.median_nanos, 42_f64);
+            assert_eq!(part.p95_nanos, 42_f64);
+            assert_eq!(part.stddev_nanos, 0_f64);
+            assert_eq!(part.samples, 1);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -288,18 +905,23 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::mock_part;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(mock_part(1e+6)),
+                    part_2: Some(mock_part(2e+6)),
                     total_nanos: 3_000_000_000_f64,
+                    failures: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -307,13 +929,16 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(mock_part(1e+6)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    failures: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -321,22 +946,89 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    failures: vec![],
+                    memory: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
-    mod merge {
+    mod compare {
         use crate::{
             day,
             template::timings::{Timing, Timings},
         };
 
+        use super::{get_mock_timings, mock_part};
+
+        #[test]
+        fn pairs_shared_parts() {
+            let baseline = get_mock_timings();
+            let current = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(4),
+                        parse: None,
+                        part_1: Some(mock_part(20e+6)),
+                        part_2: Some(mock_part(10e+6)),
+                        total_nanos: 3e+7,
+                        failures: vec![],
+                        memory: vec![],
+                    },
+                    Timing {
+                        day: day!(1),
+                        parse: None,
+                        part_1: Some(mock_part(15e+6)),
+                        part_2: Some(mock_part(10e+6)),
+                        total_nanos: 2.5e+7,
+                        failures: vec![],
+                        memory: vec![],
+                    },
+                    Timing {
+                        day: day!(3),
+                        parse: None,
+                        part_1: Some(mock_part(1e+6)),
+                        part_2: None,
+                        total_nanos: 1e+6,
+                        failures: vec![],
+                        memory: vec![],
+                    },
+                ],
+            };
+
+            let deltas = baseline.compare(&current);
+            assert_eq!(deltas.len(), 3);
+
+            assert_eq!((deltas[0].day, deltas[0].part), (day!(1), 1));
+            assert_eq!(deltas[0].change_nanos(), 5e+6);
+            assert_eq!(deltas[0].change_percent(), 50_f64);
+
+            assert_eq!((deltas[1].day, deltas[1].part), (day!(1), 2));
+            assert_eq!(deltas[1].change_percent(), -50_f64);
+
+            assert_eq!((deltas[2].day, deltas[2].part), (day!(4), 1));
+            assert_eq!(deltas[2].change_percent(), -50_f64);
+        }
+
+        #[test]
+        fn handles_empty_baseline() {
+            let deltas = Timings::default().compare(&get_mock_timings());
+            assert_eq!(deltas.is_empty(), true);
+        }
+    }
+
+    mod merge {
+        use crate::{
+            day,
+            template::timings::{MemoryStats, Timing, Timings},
+        };
+
         use super::get_mock_timings;
 
         #[test]
@@ -345,9 +1037,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failures: vec![],
+                    memory: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,9 +1060,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failures: vec![],
+                    memory: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -394,5 +1092,39 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn merges_memory() {
+            let stats = MemoryStats {
+                peak_bytes: 1024,
+                allocations: 2,
+                allocated_bytes: 2048,
+            };
+            let memory_timing = |day| Timing {
+                day,
+                parse: None,
+                part_1: None,
+                part_2: None,
+                total_nanos: 0_f64,
+                failures: vec![],
+                memory: vec![(1, stats)],
+            };
+
+            let timings = get_mock_timings().merge_memory(&Timings {
+                data: vec![memory_timing(day!(2)), memory_timing(day!(3))],
+            });
+
+            assert_eq!(timings.data.len(), 4);
+            // run times of stored days are kept.
+            assert_eq!(timings.data[1].day, day!(2));
+            assert_eq!(timings.data[1].total_nanos, 7e+10);
+            assert_eq!(timings.data[1].memory, vec![(1, stats)]);
+            assert_eq!(timings.data[2].day, day!(3));
+            assert_eq!(timings.data[2].part_1, None);
+
+            // a timed run does not drop stored memory figures.
+            let rerun = timings.merge(&get_mock_timings());
+            assert_eq!(rerun.data[1].memory, vec![(1, stats)]);
+        }
     }
 }
@@ -0,0 +1,224 @@
+//! Running year binaries from the workspace root.
+//! Each year crate lives in a directory called `aoc<year>` next to this crate, the year binary is run in that directory
+//! so that relative paths (`./data`, `./src/bin`) resolve as usual.
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use crate::template::{
+    answers::Verdict,
+    run_multi::{child_commands::parse_records, timing_from_results},
+    runner::{PartResult, RECORD_FILE_ENV},
+    timings::Timings,
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// A year crate in the workspace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Year {
+    pub year: u16,
+    pub path: PathBuf,
+}
+
+/// Aggregated results of running a command for one year.
+#[derive(Clone, Debug, Default)]
+pub struct YearSummary {
+    pub timings: Timings,
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+/// Find all year crates, i.e. directories named `aoc<year>` that contain a `Cargo.toml`, sorted by year.
+pub fn discover(workspace_root: &Path) -> io::Result<Vec<Year>> {
+    let mut years: Vec<Year> = fs::read_dir(workspace_root)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let year = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("aoc")?
+                .parse()
+                .ok()?;
+            let path = entry.path();
+            path.join("Cargo.toml")
+                .exists()
+                .then_some(Year { year, path })
+        })
+        .collect();
+
+    years.sort_unstable_by_key(|y| y.year);
+    Ok(years)
+}
+
+/// Run the year binary with the given arguments and collect the results of all parts that it ran.
+/// The returned flag is `false` if the year binary did not exit successfully.
+pub fn run_year(year: &Year, args: &[String]) -> io::Result<(bool, YearSummary)> {
+    let record_path = env::temp_dir().join(format!("aoc-{}-{}.jsonl", process::id(), year.year));
+    match fs::remove_file(&record_path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--"])
+        .args(args)
+        .current_dir(&year.path)
+        .env("AOC_YEAR", year.year.to_string())
+        .env(RECORD_FILE_ENV, &record_path)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()?;
+
+    let records = match fs::read_to_string(&record_path) {
+        Ok(records) => {
+            fs::remove_file(&record_path)?;
+            records
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    Ok((status.success(), summarize(&parse_records(&records))))
+}
+
+fn summarize(results: &[PartResult]) -> YearSummary {
+    let mut by_day: BTreeMap<Day, Vec<PartResult>> = BTreeMap::new();
+    for result in results {
+        by_day.entry(result.day).or_default().push(result.clone());
+    }
+
+    let mut summary = YearSummary {
+        timings: Timings {
+            data: by_day
+                .iter()
+                .map(|(day, results)| timing_from_results(*day, results))
+                .collect(),
+        },
+        ..YearSummary::default()
+    };
+
+    for verdict in results.iter().filter_map(|r| r.verdict.as_ref()) {
+        match verdict {
+            Verdict::Pass => summary.passed += 1,
+            Verdict::Fail { .. } => summary.failed += 1,
+            Verdict::Missing => summary.missing += 1,
+        }
+    }
+
+    summary
+}
+
+/// Run the year binary of each year in turn and print a summary table.
+/// Exits with a non-zero code if any of the year binaries failed.
+pub fn run_all(years: &[Year], args: &[String]) {
+    let mut summaries: Vec<(u16, bool, YearSummary)> = vec![];
+
+    for (i, year) in years.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}{}{ANSI_RESET}", year.year);
+        println!("======");
+
+        match run_year(year, args) {
+            Ok((success, summary)) => summaries.push((year.year, success, summary)),
+            Err(e) => {
+                eprintln!("failed to run year {}: {e}", year.year);
+                summaries.push((year.year, false, YearSummary::default()));
+            }
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+
+    for (year, success, summary) in &summaries {
+        let stars: usize = summary
+            .timings
+            .data
+            .iter()
+            .map(|t| usize::from(t.part_1.is_some()) + usize::from(t.part_2.is_some()))
+            .sum();
+
+        let mut line = format!(
+            "{year}: {stars} parts solved in {ANSI_ITALIC}{:.2}ms{ANSI_RESET}",
+            summary.timings.total_millis()
+        );
+
+        if summary.passed + summary.failed + summary.missing > 0 {
+            line.push_str(&format!(
+                ", {} passed, {} failed, {} missing",
+                summary.passed, summary.failed, summary.missing
+            ));
+        }
+
+        if !success {
+            line.push_str(" (failed)");
+        }
+
+        println!("{line}");
+    }
+
+    let total_millis: f64 = summaries
+        .iter()
+        .map(|(_, _, s)| s.timings.total_millis())
+        .sum();
+    println!("\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+
+    if summaries.iter().any(|(_, success, _)| !success) {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{discover, summarize};
+    use crate::template::run_multi::child_commands::parse_records;
+
+    #[test]
+    fn discovers_year_crates() {
+        let root = env::temp_dir().join(format!("aoc-years-{}", process::id()));
+        for dir in ["aoc2024", "aoc2015", "aoc_utils", "aoc2023"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for dir in ["aoc2024", "aoc2015", "aoc_utils"] {
+            fs::write(root.join(dir).join("Cargo.toml"), "").unwrap();
+        }
+
+        let years = discover(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            years.iter().map(|y| y.year).collect::<Vec<_>>(),
+            vec![2015, 2024]
+        );
+        assert_eq!(years[1].path, root.join("aoc2024"));
+    }
+
+    #[test]
+    fn summarizes_records() {
+        let records = parse_records(concat!(
+            r#"{ "day": "02", "part": 1, "answer": "4", "timing": { "mean_nanos": 20, "median_nanos": 20, "min_nanos": 20, "max_nanos": 20, "samples": 1 }, "verdict": "fail (expected 5)" }"#,
+            "\n",
+            r#"{ "day": "01", "part": 1, "answer": "1", "timing": { "mean_nanos": 10, "median_nanos": 10, "min_nanos": 10, "max_nanos": 10, "samples": 1 }, "verdict": "pass" }"#,
+            "\n",
+            r#"{ "day": "01", "part": 2, "answer": "2", "timing": { "mean_nanos": 30, "median_nanos": 30, "min_nanos": 30, "max_nanos": 30, "samples": 1 }, "verdict": "missing" }"#,
+            "\n",
+        ));
+
+        let summary = summarize(&records);
+        assert_eq!(summary.timings.data.len(), 2);
+        assert_eq!(summary.timings.data[0].total_nanos, 40_f64);
+        assert_eq!(summary.timings.data[1].total_nanos, 20_f64);
+        assert_eq!((summary.passed, summary.failed, summary.missing), (1, 1, 1));
+    }
+}
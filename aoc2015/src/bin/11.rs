@@ -19,14 +19,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let contents = &advent_of_code::template::read_file("examples", DAY);
+        let contents = &advent_of_code::template::read_file("examples", DAY).unwrap();
         let result = part_one(&contents);
         assert_eq!(result, Some("cqjxxyzz"));
     }
 
     #[test]
     fn test_part_two() {
-        let contents = &advent_of_code::template::read_file("examples", DAY);
+        let contents = &advent_of_code::template::read_file("examples", DAY).unwrap();
         let result = part_two(&contents);
         assert_eq!(result, Some("cqkaabcc"));
     }
@@ -1,6 +1,12 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-advent_of_code::solution!(10);
+#[path = "10/softwaregore.rs"]
+mod softwaregore;
+
+advent_of_code::solution!(
+    10,
+    variants = [softwaregore(softwaregore::part_one, softwaregore::part_two)]
+);
 
 #[derive(Clone, Copy, Debug)]
 enum Direction {
@@ -238,7 +244,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(8));
     }
 
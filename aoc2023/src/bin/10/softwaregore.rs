@@ -6,8 +6,6 @@ use std::{
     collections::{HashSet, VecDeque},
 };
 
-advent_of_code::solution!(10);
-
 #[derive(Clone, Copy, Debug)]
 enum Direction {
     North,
@@ -410,7 +408,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&advent_of_code::template::read_file("examples", crate::DAY).unwrap());
         assert_eq!(result, Some(8));
     }
 
@@ -303,14 +303,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(11687500));
     }
 
     // example doesn't work for part 2
     // #[test]
     // fn test_part_two() {
-    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
     //     assert_eq!(result, None);
     // }
 }
@@ -185,13 +185,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(42));
     }
 
     // #[test]
     // fn test_part_two() {
-    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+    //     let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
     //     assert_eq!(result, None);
     // }
 }
@@ -1,8 +1,8 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = parse);
 
-fn split_input(input: &str) -> (Vec<u64>, Vec<u64>) {
+pub fn parse(input: &str) -> (Vec<u64>, Vec<u64>) {
     input
         .lines()
         .map(|l| {
@@ -14,8 +14,8 @@ fn split_input(input: &str) -> (Vec<u64>, Vec<u64>) {
         .collect::<(Vec<u64>, Vec<u64>)>()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (mut v1, mut v2) = split_input(input);
+pub fn part_one((v1, v2): &(Vec<u64>, Vec<u64>)) -> Option<u64> {
+    let (mut v1, mut v2) = (v1.clone(), v2.clone());
     v1.sort();
     v2.sort();
     Some(
@@ -26,20 +26,19 @@ pub fn part_one(input: &str) -> Option<u64> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (v1, v2) = split_input(input);
+pub fn part_two((v1, v2): &(Vec<u64>, Vec<u64>)) -> Option<u64> {
     let max = v2
         .iter()
         .max()
         .copied()
         .expect("There should be a max number") as usize;
-    let counts = v2.into_iter().fold(vec![0_u8; max], |mut acc, x| {
+    let counts = v2.iter().fold(vec![0_u8; max], |mut acc, &x| {
         acc[x as usize - 1] += 1;
         acc
     });
     Some(
-        v1.into_iter()
-            .map(|x| x * counts[x as usize - 1] as u64)
+        v1.iter()
+            .map(|&x| x * counts[x as usize - 1] as u64)
             .sum::<u64>(),
     )
 }
@@ -50,13 +49,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+        ));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+        ));
         assert_eq!(result, Some(31));
     }
 }
@@ -1,16 +1,12 @@
+use advent_of_code::template::Params;
 use aoc_utils::*;
 use regex::Regex;
 
 advent_of_code::solution!(14);
 
-#[cfg(not(test))]
+// size of the real input, examples override it with the `width` and `height` params.
 const WIDTH: i64 = 101;
-#[cfg(test)]
-const WIDTH: i64 = 11;
-#[cfg(not(test))]
 const HEIGHT: i64 = 103;
-#[cfg(test)]
-const HEIGHT: i64 = 7;
 
 #[derive(Clone, Copy, Debug)]
 struct Bot {
@@ -18,83 +14,57 @@ struct Bot {
     vel: (i64, i64),
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+fn parse_bots(input: &str) -> Result<Vec<Bot>, ParseError> {
     let re = Regex::new(r"^p=(\d{1,3}),(\d{1,3}) v=(-?\d{1,3}),(-?\d{1,3})$")
         .expect("Regex should be valid");
-    let bots = input.regex_mlines(re, |c| {
-        let px = c.get_num::<i64>(1);
-        let py = c.get_num::<i64>(2);
-        let vx = c.get_num::<i64>(3);
-        let vy = c.get_num::<i64>(4);
-        Bot {
-            pos: (px, py),
-            vel: (vx, vy),
-        }
-    });
+    input.try_regex_mlines(re, |c| {
+        Ok(Bot {
+            pos: (c.try_get_num(1)?, c.try_get_num(2)?),
+            vel: (c.try_get_num(3)?, c.try_get_num(4)?),
+        })
+    })
+}
+
+pub fn part_one(input: &str, params: &Params) -> Result<Option<u64>, ParseError> {
+    let width = params.get_or("width", WIDTH);
+    let height = params.get_or("height", HEIGHT);
+
+    let bots = parse_bots(input)?;
     let mut quads = [0; 4];
     bots.into_iter().for_each(|b| {
-        let mut fx = (b.pos.0 + (b.vel.0 * 100)) % WIDTH;
+        let mut fx = (b.pos.0 + (b.vel.0 * 100)) % width;
         if fx < 0 {
-            fx += WIDTH;
+            fx += width;
         }
-        let mut fy = (b.pos.1 + (b.vel.1 * 100)) % HEIGHT;
+        let mut fy = (b.pos.1 + (b.vel.1 * 100)) % height;
         if fy < 0 {
-            fy += HEIGHT;
+            fy += height;
         }
         match (fx, fy) {
-            (x, y) if x < WIDTH / 2 && y < HEIGHT / 2 => {
+            (x, y) if x < width / 2 && y < height / 2 => {
                 quads[0] += 1;
             }
-            (x, y) if x > WIDTH / 2 && y < HEIGHT / 2 => {
+            (x, y) if x > width / 2 && y < height / 2 => {
                 quads[1] += 1;
             }
-            (x, y) if x < WIDTH / 2 && y > HEIGHT / 2 => {
+            (x, y) if x < width / 2 && y > height / 2 => {
                 quads[2] += 1;
             }
-            (x, y) if x > WIDTH / 2 && y > HEIGHT / 2 => {
+            (x, y) if x > width / 2 && y > height / 2 => {
                 quads[3] += 1;
             }
             (_, _) => {}
         }
     });
-    Some(quads[0] * quads[1] * quads[2] * quads[3])
+    Ok(Some(quads[0] * quads[1] * quads[2] * quads[3]))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let re = Regex::new(r"^p=(\d{1,3}),(\d{1,3}) v=(-?\d{1,3}),(-?\d{1,3})$")
-        .expect("Regex should be valid");
-    let bots = input.regex_mlines(re, |c| {
-        let px = c.get_num::<i64>(1);
-        let py = c.get_num::<i64>(2);
-        let vx = c.get_num::<i64>(3);
-        let vy = c.get_num::<i64>(4);
-        Bot {
-            pos: (px, py),
-            vel: (vx, vy),
-        }
-    });
+pub fn part_two(input: &str, params: &Params) -> Result<Option<u64>, ParseError> {
+    let width = params.get_or("width", WIDTH);
+    let height = params.get_or("height", HEIGHT);
 
-    // pattern should repeat every HEIGHT * WIDTH steps... but when does it start?
-    for i in 0..103 {
-        let mut map = vec![vec!['.'; 101]; 103];
-        bots.iter().for_each(|b| {
-            let mut fx = (b.pos.0 + (b.vel.0 * i)) % WIDTH;
-            if fx < 0 {
-                fx += WIDTH;
-            }
-            let mut fy = (b.pos.1 + (b.vel.1 * i)) % HEIGHT;
-            if fy < 0 {
-                fy += HEIGHT;
-            }
-            map[fy as usize][fx as usize] = '0'
-        });
-        // println!("\n\n----- {} ------", i);
-        #[allow(unused_variables)]
-        map.iter().for_each(|v| {
-            // v.iter().for_each(|c| print!("{c}"));
-            // print!("\n");
-        });
-    }
+    // the bots are only validated, the answer follows from the patterns below.
+    parse_bots(input)?;
 
     // manually looking at output, see some sort of pattern at 19 and a different one at 70
     //   pattern @ 19: Horizontal "ribbon"
@@ -108,39 +78,49 @@ pub fn part_two(input: &str) -> Option<u64> {
     //
     // y = (103x - 51)/101
 
-    // NOTE: These constants (19 & 70) will differ for different input - use the prints to find
-    // where your patterns occur
+    // NOTE: These constants (19 & 70) will differ for different input - print the map after each
+    // of the first `height` steps to find where your patterns occur
     const VERTICAL_PATTERN: f64 = 19.0;
     const HORIZONTAL_PATTERN: f64 = 70.0;
 
     let mut x = 0;
-    for i in 1..101 {
+    for i in 1..width {
         let res =
-            (HEIGHT as f64 * (i as f64) - (HORIZONTAL_PATTERN - VERTICAL_PATTERN)) / WIDTH as f64;
-        // find first whole number - this is our x in x * 103 + 19 = res
+            (height as f64 * (i as f64) - (HORIZONTAL_PATTERN - VERTICAL_PATTERN)) / width as f64;
+        // find first whole number - this is our x in x * height + 19 = res
         if res.fract() == 0.0 {
             x = i;
             break;
         }
     }
-    let x = 103 * x + 19;
-    Some(x)
+    let x = height * x + VERTICAL_PATTERN as i64;
+    Ok(Some(x as u64))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example_params() -> Params {
+        Params::from([("width", "11"), ("height", "7")])
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
+        assert_eq!(result, Ok(Some(12)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
         // doesn't actually make a tree in example input
-        assert_eq!(result, Some(122));
+        assert_eq!(result, Ok(Some(26)));
     }
 }
@@ -1,18 +1,14 @@
 use std::collections::VecDeque;
 
+use advent_of_code::template::Params;
 use aoc_utils::*;
 use itertools::Itertools;
 
 advent_of_code::solution!(18);
 
-#[cfg(not(test))]
+// values of the real input, examples override them with the `blocks` and `size` params.
 const NUMBER_OF_BLOCKS: usize = 1024;
-#[cfg(test)]
-const NUMBER_OF_BLOCKS: usize = 12;
-#[cfg(not(test))]
 const SIZE_OF_MAP: usize = 71;
-#[cfg(test)]
-const SIZE_OF_MAP: usize = 7;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Loc {
@@ -47,7 +43,10 @@ fn find_shortest_path_len(
     None
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
+    let blocks = params.get_or("blocks", NUMBER_OF_BLOCKS);
+    let size = params.get_or("size", SIZE_OF_MAP);
+
     let points = input.mlines(|l| {
         l.split_once(",")
             .map(|(x, y)| {
@@ -58,11 +57,11 @@ pub fn part_one(input: &str) -> Option<u64> {
             })
             .expect("Should split")
     });
-    let mut map = vec![vec![Loc::Empty; SIZE_OF_MAP]; SIZE_OF_MAP];
-    let mut seen = vec![vec![false; SIZE_OF_MAP]; SIZE_OF_MAP];
+    let mut map = vec![vec![Loc::Empty; size]; size];
+    let mut seen = vec![vec![false; size]; size];
     points
         .into_iter()
-        .take(NUMBER_OF_BLOCKS)
+        .take(blocks)
         .for_each(|p| map[p.0][p.1] = Loc::Block);
     find_shortest_path_len(Point(0, 0), 0, &map, &mut seen)
 }
@@ -97,7 +96,10 @@ fn find_shortest_path(
     None
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, params: &Params) -> Option<String> {
+    let blocks = params.get_or("blocks", NUMBER_OF_BLOCKS);
+    let size = params.get_or("size", SIZE_OF_MAP);
+
     let points = input.mlines(|l| {
         l.split_once(",")
             .map(|(x, y)| {
@@ -108,7 +110,7 @@ pub fn part_two(input: &str) -> Option<String> {
             })
             .expect("Should split")
     });
-    let mut map = vec![vec![Loc::Empty; SIZE_OF_MAP]; SIZE_OF_MAP];
+    let mut map = vec![vec![Loc::Empty; size]; size];
     let mut curr_short_path = map
         .iter()
         .enumerate()
@@ -116,12 +118,12 @@ pub fn part_two(input: &str) -> Option<String> {
         .collect_vec();
     points
         .iter()
-        .take(NUMBER_OF_BLOCKS)
+        .take(blocks)
         .for_each(|p| map[p.0][p.1] = Loc::Block);
-    points.into_iter().skip(NUMBER_OF_BLOCKS).find_map(|p| {
+    points.into_iter().skip(blocks).find_map(|p| {
         map[p.0][p.1] = Loc::Block;
         if curr_short_path.contains(&p) {
-            let mut seen = vec![vec![false; SIZE_OF_MAP]; SIZE_OF_MAP];
+            let mut seen = vec![vec![false; size]; size];
             if let Some(v) = find_shortest_path(Point(0, 0), 0, &map, &mut seen) {
                 curr_short_path = v;
                 None
@@ -138,15 +140,25 @@ pub fn part_two(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    fn example_params() -> Params {
+        Params::from([("blocks", "12"), ("size", "7")])
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
         assert_eq!(result, Some("6,1".to_string()));
     }
 }
@@ -1,13 +1,12 @@
 use std::collections::VecDeque;
 
+use advent_of_code::template::Params;
 use aoc_utils::*;
 
 advent_of_code::solution!(20);
 
-#[cfg(not(test))]
+// cutoff of the real input, examples override it with the `cutoff` param.
 const SAVE_CUTOFF: usize = 100;
-#[cfg(test)]
-const SAVE_CUTOFF: usize = 50;
 
 fn find_path(start: Point, end: Point, map: &[Vec<char>]) -> Vec<Point> {
     let bounds = Bounds(map.len() - 1, map[0].len() - 1);
@@ -38,17 +37,17 @@ fn find_path(start: Point, end: Point, map: &[Vec<char>]) -> Vec<Point> {
     path
 }
 
-fn find_cheats(path: &[Point], max_dist: usize) -> u64 {
+fn find_cheats(path: &[Point], max_dist: usize, cutoff: usize) -> u64 {
     path.iter()
         .enumerate()
-        .take(path.len() - SAVE_CUTOFF - 1)
+        .take(path.len() - cutoff - 1)
         .fold(0, |mut acc, (i, p)| {
             path.iter()
                 .enumerate()
-                .skip(i + SAVE_CUTOFF + 2)
+                .skip(i + cutoff + 2)
                 .for_each(|(j, p2)| {
                     let d = dist(*p, *p2);
-                    if j - i - d >= SAVE_CUTOFF && d <= max_dist {
+                    if j - i - d >= cutoff && d <= max_dist {
                         acc += 1;
                     }
                 });
@@ -56,35 +55,45 @@ fn find_cheats(path: &[Point], max_dist: usize) -> u64 {
         })
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
     let map = input.c_map();
     let start = find_point(&map, 'S');
     let end = find_point(&map, 'E');
     let path = find_path(start, end, &map);
-    Some(find_cheats(&path, 2))
+    Some(find_cheats(&path, 2, params.get_or("cutoff", SAVE_CUTOFF)))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
     let map = input.c_map();
     let start = find_point(&map, 'S');
     let end = find_point(&map, 'E');
     let path = find_path(start, end, &map);
-    Some(find_cheats(&path, 20))
+    Some(find_cheats(&path, 20, params.get_or("cutoff", SAVE_CUTOFF)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example_params() -> Params {
+        Params::from([("cutoff", "50")])
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
         assert_eq!(result, Some(1));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &example_params(),
+        );
         assert_eq!(result, Some(285));
     }
 }
@@ -1,6 +1,6 @@
 use aoc_utils::*;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, variants = [original(part_one, part_two_original)]);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let mut map = input.c_map();
@@ -83,13 +83,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(40));
     }
 }
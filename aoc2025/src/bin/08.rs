@@ -3,6 +3,7 @@ use std::{
     mem,
 };
 
+use advent_of_code::template::Params;
 use aoc_utils::*;
 use itertools::Itertools;
 
@@ -64,7 +65,7 @@ fn distance(a: (u64, u64, u64), b: (u64, u64, u64)) -> u64 {
     (x + y + z).isqrt()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
     let points = input.mlines(|s| {
         s.split(",")
             .map(|n| n.parse::<u64>().expect("Should get int"))
@@ -77,10 +78,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     circuits.push(HashSet::new());
     let mut point_to_circuit = HashMap::<(u64, u64, u64), usize>::new();
 
-    #[cfg(test)]
-    let num_conns = 10;
-    #[cfg(not(test))]
-    let num_conns = 1000;
+    // the examples connect fewer pairs, see the `connections` param.
+    let num_conns = params.get_or("connections", 1000);
     let conns = points
         .into_iter()
         .tuple_combinations()
@@ -132,13 +131,16 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).unwrap(),
+            &Params::from([("connections", "10")]),
+        );
         assert_eq!(result, Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).unwrap());
         assert_eq!(result, Some(25272));
     }
 }
@@ -0,0 +1,120 @@
+//! Generates the registry of solutions that are linked into a year binary.
+//! This crate has no dependencies, as it is compiled for the build script of every year crate.
+use std::fmt::Write;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// The last day of the longest event. Shorter events simply have no modules for the later days.
+const LAST_DAY: u8 = 25;
+
+/// Writes `solutions.rs` to `OUT_DIR`, declaring the constant `SOLUTIONS`.
+/// Meant to be called from the build script of a year crate, and included in its `main.rs`.
+///
+/// Every `src/bin/NN.rs` that uses the `solution!` macro is included as a module, unless it
+/// declares crate-level attributes (e.g. `#![feature(...)]`), which are only valid in a crate root.
+/// Days that are not linked are run as child processes by `run_multi` instead.
+pub fn generate() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR should be set");
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set");
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let code = solutions_code(&linked_days(&bin_dir));
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), code)
+        .expect("should be able to write solutions.rs");
+}
+
+/// The days in `bin_dir` that can be linked in-process, with the path of their module.
+fn linked_days(bin_dir: &Path) -> Vec<(u8, PathBuf)> {
+    (1..=LAST_DAY)
+        .filter_map(|day| {
+            let path = bin_dir.join(format!("{day:02}.rs"));
+            let source = fs::read_to_string(&path).ok()?;
+            is_linkable(&source).then_some((day, path))
+        })
+        .collect()
+}
+
+/// The contents of `solutions.rs` for the `linked` days.
+fn solutions_code(linked: &[(u8, PathBuf)]) -> String {
+    let mut code = String::new();
+
+    // NOTE: tests already run as part of each solution binary,
+    // and `dhat-heap` installs a global allocator per solution.
+    code.push_str("#[cfg(not(any(test, feature = \"dhat-heap\")))]\nmod linked {\n");
+    for (day, path) in linked {
+        let _ = writeln!(
+            code,
+            "    #[path = {:?}]\n    #[allow(unused)]\n    mod day_{day:02};",
+            path.display().to_string()
+        );
+    }
+    code.push_str(
+        "\n    pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n",
+    );
+    for (day, _) in linked {
+        let _ = writeln!(code, "        day_{day:02}::SOLUTION,");
+    }
+    code.push_str("    ];\n}\n\n");
+    code.push_str(
+        "#[cfg(not(any(test, feature = \"dhat-heap\")))]\npub use linked::SOLUTIONS;\n\n",
+    );
+    code.push_str("#[cfg(any(test, feature = \"dhat-heap\"))]\n");
+    code.push_str("pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];\n");
+    code
+}
+
+fn is_linkable(source: &str) -> bool {
+    source.contains("solution!(") && !source.lines().any(|l| l.trim_start().starts_with("#!["))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, process};
+
+    use super::{is_linkable, linked_days, solutions_code};
+
+    #[test]
+    fn links_days_without_crate_attributes() {
+        assert!(is_linkable("advent_of_code::solution!(1);\n"));
+        assert!(!is_linkable(
+            "#![feature(iter_map_windows)]\nadvent_of_code::solution!(2);\n"
+        ));
+        assert!(!is_linkable("fn main() {}\n"));
+
+        let dir = env::temp_dir().join(format!("aoc-registry-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.rs"), "advent_of_code::solution!(1);\n").unwrap();
+        fs::write(
+            dir.join("02.rs"),
+            "#![feature(iter_map_windows)]\nadvent_of_code::solution!(2);\n",
+        )
+        .unwrap();
+
+        let linked = linked_days(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(linked, vec![(1, dir.join("01.rs"))]);
+    }
+
+    #[test]
+    fn generates_solutions() {
+        let code = solutions_code(&[(1, PathBuf::from("/y/src/bin/01.rs"))]);
+
+        assert!(code.contains(
+            "    #[path = \"/y/src/bin/01.rs\"]\n    #[allow(unused)]\n    mod day_01;\n"
+        ));
+        assert!(code.contains("        day_01::SOLUTION,\n"));
+        assert!(code.ends_with(
+            "#[cfg(any(test, feature = \"dhat-heap\"))]\n\
+            pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];\n"
+        ));
+
+        let empty = solutions_code(&[]);
+        assert!(!empty.contains("mod day_"));
+        assert!(empty.contains("pub use linked::SOLUTIONS;"));
+    }
+}
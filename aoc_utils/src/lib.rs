@@ -1,6 +1,6 @@
 use std::{
     cmp::Ordering,
-    fmt::Debug,
+    fmt::{Debug, Display},
     hash::{Hash, Hasher},
     str::FromStr,
 };
@@ -313,14 +313,106 @@ where
     if y < 0 { x - (-y) as u64 } else { x + y as u64 }
 }
 
+/// Error while parsing puzzle input, with the position it occurred at (if known)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the input
+    pub line: Option<usize>,
+    /// 1-based column of the line, in characters
+    pub column: Option<usize>,
+    /// 0-based byte offset in the line, until the line is known and it is converted to a column
+    pub offset: Option<usize>,
+    /// What went wrong, e.g. `expected digit`
+    pub message: String,
+    /// The offending part of the input
+    pub context: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column: None,
+            offset: None,
+            message: message.into(),
+            context: None,
+        }
+    }
+
+    /// Set the offending part of the input
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Set the line, unless already known
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Set the column, unless already known
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column.get_or_insert(column);
+        self
+    }
+
+    /// Set the byte offset in the line, unless already known
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Convert the byte offset to a column of `line`, which may contain multi-byte characters
+    pub fn in_line(mut self, line: &str) -> Self {
+        if let Some(before) = self.offset.and_then(|offset| line.get(..offset)) {
+            self.offset = None;
+            self = self.at_column(before.chars().count() + 1);
+        }
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column, self.offset) {
+            (Some(line), Some(column), _) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None, Some(offset)) => write!(f, "line {line}, byte {offset}: ")?,
+            (Some(line), None, None) => write!(f, "line {line}: ")?,
+            (None, Some(column), _) => write!(f, "column {column}: ")?,
+            (None, None, Some(offset)) => write!(f, "byte {offset}: ")?,
+            (None, None, None) => {}
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(context) = &self.context {
+            write!(f, " (`{context}`)")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Parse str to u64
 pub fn ufroms(s: &str) -> u64 {
-    s.parse().expect("Should parse number")
+    try_ufroms(s).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse str to u64, failing with a [`ParseError`]
+pub fn try_ufroms(s: &str) -> Result<u64, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::new("expected number").with_context(s))
 }
 
 /// Parse char digit to u32
 pub fn ufromc(c: char) -> u32 {
-    c.to_digit(10).expect("Should parse number")
+    try_ufromc(c).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Parse char digit to u32, failing with a [`ParseError`]
+pub fn try_ufromc(c: char) -> Result<u32, ParseError> {
+    c.to_digit(10)
+        .ok_or_else(|| ParseError::new("expected digit").with_context(c))
 }
 
 /// Input parsing convenience methods
@@ -358,6 +450,26 @@ pub trait InputParse<'a> {
     fn mblocks<F, U>(self, f: F) -> Vec<U>
     where
         F: FnMut(&str) -> U;
+
+    /// Map fallible function over each line, errors get the line number
+    fn try_mlines<F, U>(self, f: F) -> Result<Vec<U>, ParseError>
+    where
+        F: FnMut(&str) -> Result<U, ParseError>;
+
+    /// Apply regex to each line and map captures with fallible function, errors get the line number
+    fn try_regex_mlines<F, U>(self, re: Regex, f: F) -> Result<Vec<U>, ParseError>
+    where
+        F: FnMut(Captures) -> Result<U, ParseError>;
+
+    /// Parse to grid with fallible mapping function, errors get the line and column
+    fn try_c_mmap<F, U>(self, f: F) -> Result<Vec<Vec<U>>, ParseError>
+    where
+        F: FnMut(char) -> Result<U, ParseError>;
+
+    /// Split lines by whitespace and map each token with fallible function, errors get the line number
+    fn try_ws_mmap<F, U>(self, f: F) -> Result<Vec<Vec<U>>, ParseError>
+    where
+        F: FnMut(&str) -> Result<U, ParseError>;
 }
 
 impl<'a> InputParse<'a> for &'a str {
@@ -368,13 +480,12 @@ impl<'a> InputParse<'a> for &'a str {
         self.lines().map(f).collect_vec()
     }
 
-    fn regex_mlines<F, U>(self, re: Regex, f: F) -> Vec<U>
+    fn regex_mlines<F, U>(self, re: Regex, mut f: F) -> Vec<U>
     where
         F: FnMut(Captures) -> U + Copy,
     {
-        self.lines()
-            .map(|l| re.captures(l).map(f).expect("Regex should work"))
-            .collect_vec()
+        self.try_regex_mlines(re, |c| Ok(f(c)))
+            .unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn c_map(self) -> Vec<Vec<char>> {
@@ -415,6 +526,47 @@ impl<'a> InputParse<'a> for &'a str {
     {
         self.split("\n\n").map(f).collect_vec()
     }
+
+    fn try_mlines<F, U>(self, mut f: F) -> Result<Vec<U>, ParseError>
+    where
+        F: FnMut(&str) -> Result<U, ParseError>,
+    {
+        self.lines()
+            .enumerate()
+            .map(|(i, l)| f(l).map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+
+    fn try_regex_mlines<F, U>(self, re: Regex, mut f: F) -> Result<Vec<U>, ParseError>
+    where
+        F: FnMut(Captures) -> Result<U, ParseError>,
+    {
+        self.try_mlines(|l| {
+            let captures = re.captures(l).ok_or_else(|| {
+                ParseError::new(format!("expected line to match `{re}`")).with_context(l)
+            })?;
+            f(captures).map_err(|e| e.in_line(l))
+        })
+    }
+
+    fn try_c_mmap<F, U>(self, mut f: F) -> Result<Vec<Vec<U>>, ParseError>
+    where
+        F: FnMut(char) -> Result<U, ParseError>,
+    {
+        self.try_mlines(|l| {
+            l.chars()
+                .enumerate()
+                .map(|(i, c)| f(c).map_err(|e| e.at_column(i + 1)))
+                .collect()
+        })
+    }
+
+    fn try_ws_mmap<F, U>(self, mut f: F) -> Result<Vec<Vec<U>>, ParseError>
+    where
+        F: FnMut(&str) -> Result<U, ParseError>,
+    {
+        self.try_mlines(|l| l.split_whitespace().map(&mut f).collect())
+    }
 }
 
 // Convenience methods for working with maps
@@ -439,6 +591,12 @@ pub trait ExtractNum {
     where
         U: FromStr,
         <U as FromStr>::Err: Debug;
+
+    /// Parse capture group at position to number type, failing with a [`ParseError`] (at the byte offset of the group,
+    /// which [`InputParse::try_regex_mlines`] converts to a column)
+    fn try_get_num<U>(&self, pos: usize) -> Result<U, ParseError>
+    where
+        U: FromStr;
 }
 
 impl ExtractNum for Captures<'_> {
@@ -447,11 +605,23 @@ impl ExtractNum for Captures<'_> {
         U: FromStr,
         <U as FromStr>::Err: Debug,
     {
-        self.get(pos)
-            .expect("Should get px")
-            .as_str()
-            .parse::<U>()
-            .expect("Capture should be int")
+        self.try_get_num(pos).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_get_num<U>(&self, pos: usize) -> Result<U, ParseError>
+    where
+        U: FromStr,
+    {
+        let group = self.get(pos).ok_or_else(|| {
+            ParseError::new(format!("expected capture group {pos}"))
+                .with_context(self.get(0).map_or("", |m| m.as_str()))
+        })?;
+
+        group.as_str().parse::<U>().map_err(|_| {
+            ParseError::new("expected number")
+                .with_context(group.as_str())
+                .at_offset(group.start())
+        })
     }
 }
 
@@ -552,6 +722,52 @@ mod test {
         assert_eq!(iadd64(12, 6), 18);
     }
 
+    #[test]
+    fn parse_errors_have_position() {
+        let err = "12\n3x4".try_c_mmap(try_ufromc).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: expected digit (`x`)");
+
+        let err = "1 2\n3 four".try_ws_mmap(try_ufroms).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected number (`four`)");
+
+        assert_eq!(
+            "1 2\n3 4".try_ws_mmap(try_ufroms),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn regex_parse_errors_have_position() {
+        let re = Regex::new(r"^p=(\d+),(\w+)$").unwrap();
+
+        let err = "p=1,2\nq=3,4"
+            .try_regex_mlines(re.clone(), |c| c.try_get_num::<u64>(1))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected line to match `^p=(\\d+),(\\w+)$` (`q=3,4`)"
+        );
+
+        let err = "p=1,2\np=3,x"
+            .try_regex_mlines(re, |c| c.try_get_num::<u64>(2))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 5: expected number (`x`)");
+
+        // columns count characters, not bytes.
+        let re = Regex::new(r"^(\w)=(\d+),(\w+)$").unwrap();
+        let err = "ä=1,x"
+            .try_regex_mlines(re.clone(), |c| c.try_get_num::<u64>(3))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 5: expected number (`x`)");
+
+        let err = re
+            .captures("ä=1,x")
+            .unwrap()
+            .try_get_num::<u64>(3)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "byte 5: expected number (`x`)");
+    }
+
     #[test]
     fn test_dir_neighbors() {
         let bounds = Bounds(4, 4);